@@ -19,7 +19,9 @@ export token_c=$(resim run manifests/make_tokens.rtm | awk -F'Resource: ' 'NF>1{
 export token_name="token_d"
 export token_d=$(resim run manifests/make_tokens.rtm | awk -F'Resource: ' 'NF>1{print $2}')
 
-export dex_component_address=$(resim run manifests/komo_dex/new.rtm | awk -F'Component: ' 'NF>1{print $2}')
+export dex_output=$(resim run manifests/komo_dex/new.rtm)
+export dex_component_address=$(echo "$dex_output" | awk -F'Component: ' 'NF>1{print $2}')
+export admin_badge=$(echo "$dex_output" | awk -F'Resource: ' 'NF>1{print $2}')
 
 echo "Account Address: $account_address"
 echo "Package Address: $package_address"
@@ -27,4 +29,5 @@ echo "Token A: $token_a"
 echo "Token B: $token_b"
 echo "Token C: $token_c"
 echo "Token D: $token_d"
-echo "DEX Component Address: $dex_component_address"
\ No newline at end of file
+echo "DEX Component Address: $dex_component_address"
+echo "DEX Admin Badge: $admin_badge"
\ No newline at end of file
@@ -0,0 +1,1241 @@
+use scrypto::prelude::*;
+use crate::utils::*;
+
+/// The largest tick that a position may be bounded by. The price at this tick is `1.0001^200000`, which is roughly
+/// `4.85 * 10^8`, and the price at the negative of this tick is roughly `2 * 10^-9`. These bounds keep the square root
+/// prices well within the precision of `Decimal`.
+const MAX_TICK: i32 = 200000;
+
+/// The square root of `1.0001`, which is the ratio between the square root prices of two neighbouring ticks.
+fn sqrt_tick_base() -> Decimal {
+    return dec!("1.000049998750062496");
+}
+
+/// The relative margin that the liquidity and the amounts of a new position, and the steps of a swap, are rounded by in
+/// favour of the pool. The arithmetic of `Decimal` truncates, so without a margin a position could be credited with 
+/// slightly more liquidity than the tokens it deposited are worth, or a swap could pay out slightly more than the 
+/// curve allows, and the last position to be removed would be short. The margin is far larger than the truncation 
+/// errors of the square root prices so that it always covers them.
+fn rounding_margin() -> Decimal {
+    return dec!("0.000000000001");
+}
+
+/// Calculates the square root price at the given tick: `sqrt(1.0001^tick)`.
+fn sqrt_price_at_tick(tick: i32) -> Decimal {
+    return powi(sqrt_tick_base(), tick as i64);
+}
+
+/// Finds the largest tick whose square root price is less than or equal to the given square root price.
+/// 
+/// # Arguments:
+/// 
+/// * `sqrt_price` (Decimal) - The square root price to find the tick of.
+/// 
+/// # Returns:
+/// 
+/// * `i32` - The tick that the square root price is in, bounded to `[-MAX_TICK, MAX_TICK]`.
+fn tick_at_sqrt_price(sqrt_price: Decimal) -> i32 {
+    let mut low: i32 = -MAX_TICK;
+    let mut high: i32 = MAX_TICK;
+    while low < high {
+        let middle: i32 = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(middle) <= sqrt_price {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    return low;
+}
+
+/// Calculates the amounts of the two tokens that make up the given liquidity over a price range.
+/// 
+/// # Arguments:
+/// 
+/// * `sqrt_price` (Decimal) - The current square root price of the pool.
+/// * `sqrt_price_lower` (Decimal) - The square root price at the lower bound of the range.
+/// * `sqrt_price_upper` (Decimal) - The square root price at the upper bound of the range.
+/// * `liquidity` (Decimal) - The amount of liquidity.
+/// 
+/// # Returns:
+/// 
+/// * `(Decimal, Decimal)` - The amounts of the first and the second token.
+/// 
+/// # Note:
+/// 
+/// Below the range the liquidity is held entirely in the first token, above the range it is held entirely in the
+/// second token, and inside the range it is split between the two such that `amount0 = L * (1/√P - 1/√Pb)` and
+/// `amount1 = L * (√P - √Pa)`.
+fn amounts_for_liquidity(
+    sqrt_price: Decimal,
+    sqrt_price_lower: Decimal,
+    sqrt_price_upper: Decimal,
+    liquidity: Decimal
+) -> (Decimal, Decimal) {
+    return if sqrt_price <= sqrt_price_lower {
+        (liquidity * (sqrt_price_upper - sqrt_price_lower) / (sqrt_price_lower * sqrt_price_upper), Decimal::zero())
+    } else if sqrt_price < sqrt_price_upper {
+        (
+            liquidity * (sqrt_price_upper - sqrt_price) / (sqrt_price * sqrt_price_upper),
+            liquidity * (sqrt_price - sqrt_price_lower)
+        )
+    } else {
+        (Decimal::zero(), liquidity * (sqrt_price_upper - sqrt_price_lower))
+    };
+}
+
+/// Calculates the largest liquidity that can be provided over a price range from the given amounts of the two tokens.
+/// 
+/// # Arguments:
+/// 
+/// * `sqrt_price` (Decimal) - The current square root price of the pool.
+/// * `sqrt_price_lower` (Decimal) - The square root price at the lower bound of the range.
+/// * `sqrt_price_upper` (Decimal) - The square root price at the upper bound of the range.
+/// * `amount0` (Decimal) - The amount of the first token available.
+/// * `amount1` (Decimal) - The amount of the second token available.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The amount of liquidity.
+fn liquidity_for_amounts(
+    sqrt_price: Decimal,
+    sqrt_price_lower: Decimal,
+    sqrt_price_upper: Decimal,
+    amount0: Decimal,
+    amount1: Decimal
+) -> Decimal {
+    return if sqrt_price <= sqrt_price_lower {
+        amount0 * sqrt_price_lower * sqrt_price_upper / (sqrt_price_upper - sqrt_price_lower)
+    } else if sqrt_price < sqrt_price_upper {
+        std::cmp::min(
+            amount0 * sqrt_price * sqrt_price_upper / (sqrt_price_upper - sqrt_price),
+            amount1 / (sqrt_price - sqrt_price_lower)
+        )
+    } else {
+        amount1 / (sqrt_price_upper - sqrt_price_lower)
+    };
+}
+
+/// The state kept for every initialized tick of a concentrated pool.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct TickInfo {
+    /// The total liquidity of all of the positions that use this tick as one of their bounds.
+    pub liquidity_gross: Decimal,
+
+    /// The liquidity that becomes active when the price crosses this tick upwards, and inactive when it crosses it
+    /// downwards. This is negative for ticks that are mostly used as upper bounds.
+    pub liquidity_net: Decimal,
+
+    /// The fee growth per unit of liquidity of the first and second token on the other side of this tick from the
+    /// current price.
+    pub fee_growth_outside: (Decimal, Decimal)
+}
+
+/// The state kept for every liquidity position of a concentrated pool.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct Position {
+    /// The lower tick of the price range of the position.
+    pub lower_tick: i32,
+
+    /// The upper tick of the price range of the position.
+    pub upper_tick: i32,
+
+    /// The amount of liquidity provided by the position.
+    pub liquidity: Decimal,
+
+    /// The fee growth inside the price range of the position the last time that its fees were updated.
+    pub fee_growth_inside_last: (Decimal, Decimal),
+
+    /// The fees of the first and second token that the position has accrued and not yet collected.
+    pub tokens_owed: (Decimal, Decimal)
+}
+
+/// The data of the non-fungible tokens that represent the liquidity positions of a concentrated pool.
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct PositionData {
+    /// The lower tick of the price range of the position.
+    pub lower_tick: i32,
+
+    /// The upper tick of the price range of the position.
+    pub upper_tick: i32
+}
+
+/// The outcome of walking a swap along the ticks of a concentrated pool without changing any state.
+struct SwapComputation {
+    amount_out: Decimal,
+    amount_remaining: Decimal,
+    sqrt_price: Decimal,
+    current_tick: i32,
+    liquidity: Decimal,
+    fee_growth_global_input: Decimal,
+    protocol_amount: Decimal,
+    crossed_ticks: Vec<(i32, Decimal)>
+}
+
+impl SwapComputation {
+    /// Splits the fee of one step of a swap between the protocol and the positions active during that step.
+    /// 
+    /// # Arguments:
+    /// 
+    /// * `fee_amount` (Decimal) - The fee of the step, in input tokens.
+    /// * `protocol_fee` (Decimal) - The percentage of the fee which goes to the protocol.
+    /// * `liquidity` (Decimal) - The liquidity active during the step.
+    fn take_protocol_fee(
+        &mut self,
+        fee_amount: Decimal,
+        protocol_fee: Decimal,
+        liquidity: Decimal
+    ) {
+        let protocol_amount: Decimal = if fee_amount > Decimal::zero() {
+            fee_amount * protocol_fee / dec!("100")
+        } else {
+            Decimal::zero()
+        };
+        self.protocol_amount = self.protocol_amount + protocol_amount;
+        self.fee_growth_global_input = self.fee_growth_global_input + (fee_amount - protocol_amount) / liquidity;
+    }
+
+    /// Walks the remaining input of the swap along the given ticks, as described in `compute_swap`.
+    /// 
+    /// # Arguments:
+    /// 
+    /// * `ticks` (&BTreeMap<i32, TickInfo>) - The initialized ticks of the pool.
+    /// * `fee_to_pool` (Decimal) - The percentage fee taken out of the input.
+    /// * `protocol_fee` (Decimal) - The percentage of the fee which goes to the protocol.
+    /// * `zero_for_one` (bool) - Whether the first token is being swapped for the second.
+    fn walk_ticks(
+        &mut self,
+        ticks: &BTreeMap<i32, TickInfo>,
+        fee_to_pool: Decimal,
+        protocol_fee: Decimal,
+        zero_for_one: bool
+    ) {
+        let r: Decimal = (dec!("100") - fee_to_pool) / dec!("100");
+        while self.amount_remaining > Decimal::zero() {
+            // Finding the next initialized tick in the direction of the swap
+            let next_tick: Option<i32> = if zero_for_one {
+                ticks.range(..=self.current_tick).next_back().map(|(tick, _)| *tick)
+            } else {
+                ticks.range((self.current_tick + 1)..).next().map(|(tick, _)| *tick)
+            };
+            let next_tick: i32 = match next_tick {
+                Some(tick) => tick,
+                None => break
+            };
+            let sqrt_price_target: Decimal = sqrt_price_at_tick(next_tick);
+            let sqrt_price: Decimal = self.sqrt_price;
+            let liquidity: Decimal = self.liquidity;
+
+            // When there is no liquidity in the current range the price moves straight to the next tick
+            if liquidity > Decimal::zero() {
+                let net_amount_available: Decimal = self.amount_remaining * r;
+                let (exact_net_amount_max, exact_output_max): (Decimal, Decimal) = if zero_for_one {
+                    (
+                        liquidity * (sqrt_price - sqrt_price_target) / (sqrt_price * sqrt_price_target),
+                        liquidity * (sqrt_price - sqrt_price_target)
+                    )
+                } else {
+                    (
+                        liquidity * (sqrt_price_target - sqrt_price),
+                        liquidity * (sqrt_price_target - sqrt_price) / (sqrt_price * sqrt_price_target)
+                    )
+                };
+                let net_amount_max: Decimal = exact_net_amount_max + exact_net_amount_max * rounding_margin();
+                let output_max: Decimal = exact_output_max - exact_output_max * rounding_margin();
+
+                if net_amount_available < net_amount_max {
+                    // The swap ends inside of the current range. The movement of the price is rounded towards the
+                    // starting price and kept from passing the next tick.
+                    let new_sqrt_price: Decimal = if zero_for_one {
+                        let movement: Decimal = net_amount_available * sqrt_price * sqrt_price 
+                            / (liquidity + net_amount_available * sqrt_price);
+                        std::cmp::max(sqrt_price - (movement - movement * rounding_margin()), sqrt_price_target)
+                    } else {
+                        let movement: Decimal = net_amount_available / liquidity;
+                        std::cmp::min(sqrt_price + (movement - movement * rounding_margin()), sqrt_price_target)
+                    };
+                    let exact_amount_out: Decimal = if zero_for_one {
+                        liquidity * (sqrt_price - new_sqrt_price)
+                    } else {
+                        liquidity * (new_sqrt_price - sqrt_price) / (sqrt_price * new_sqrt_price)
+                    };
+                    self.amount_out = self.amount_out + exact_amount_out - exact_amount_out * rounding_margin();
+                    let fee_amount: Decimal = self.amount_remaining - net_amount_available;
+                    self.take_protocol_fee(fee_amount, protocol_fee, liquidity);
+                    self.amount_remaining = Decimal::zero();
+                    self.sqrt_price = new_sqrt_price;
+                    // A price moving up which is clamped onto the next tick has not crossed it, so the tick's 
+                    // liquidity is not active yet and the current tick stays below it.
+                    self.current_tick = if !zero_for_one & (new_sqrt_price == sqrt_price_target) {
+                        next_tick - 1
+                    } else {
+                        tick_at_sqrt_price(new_sqrt_price)
+                    };
+                    break;
+                }
+
+                // The swap reaches the next tick, so only the input needed to get there is used
+                let gross_amount: Decimal = std::cmp::min(net_amount_max / r, self.amount_remaining);
+                self.amount_out = self.amount_out + output_max;
+                self.take_protocol_fee(gross_amount - net_amount_max, protocol_fee, liquidity);
+                self.amount_remaining = self.amount_remaining - gross_amount;
+            }
+
+            // Crossing the tick and changing the active liquidity
+            let liquidity_net: Decimal = ticks[&next_tick].liquidity_net;
+            self.sqrt_price = sqrt_price_target;
+            self.crossed_ticks.push((next_tick, self.fee_growth_global_input));
+            if zero_for_one {
+                self.liquidity = self.liquidity - liquidity_net;
+                self.current_tick = next_tick - 1;
+            } else {
+                self.liquidity = self.liquidity + liquidity_net;
+                self.current_tick = next_tick;
+            }
+        }
+    }
+}
+
+#[blueprint]
+mod concentrated_pool {
+
+    /// ConcentratedPool encapsulate liquidity pool fields and methods for liquidity concentrated within price ranges
+    /// Every position provides liquidity between a lower and an upper tick, where the price at tick `i` is `1.0001^i`.
+    /// Within the range of the current price the pool behaves like `x * y = k` over the sum of the liquidity of all of
+    /// the positions whose ranges include the current price, which makes the liquidity far more capital efficient.
+    pub struct ConcentratedPool{
+       /// use a more flexible dynamic way to store the vault addresses.
+       /// Note: ConcentratedPool object always have exactly 2 vaults
+       vaults: HashMap<ResourceAddress, Vault>,
+
+       /// The sorted resource addresses of the two tokens. Prices are always of the first token in terms
+       /// of the second token.
+       addresses: (ResourceAddress, ResourceAddress),
+
+       /// Decimal Amount between 0 to 100 representing the percentage fee
+       /// paid to the positions whose ranges are active during a swap
+       fee_to_pool: Decimal,
+
+       /// Decimal Amount between 0 to 100 representing the percentage of the swap 
+       /// fee which is diverted to the protocol instead of the positions
+       protocol_fee: Decimal,
+
+       /// Vaults holding the protocol fees collected from swaps until they're collected
+       protocol_vaults: HashMap<ResourceAddress, Vault>,
+
+       /// Positions may only be bounded by ticks that are multiples of the tick spacing.
+       tick_spacing: i32,
+
+       /// The square root of the current price of the first token in terms of the second token.
+       sqrt_price: Decimal,
+
+       /// The largest tick whose price is less than or equal to the current price.
+       current_tick: i32,
+
+       /// The sum of the liquidity of all of the positions whose ranges include the current price.
+       liquidity: Decimal,
+
+       /// The total fees of the first and second token earned per unit of liquidity since the pool was created.
+       fee_growth_global: (Decimal, Decimal),
+
+       /// The state of all of the ticks which are used as a bound by at least one position.
+       ticks: BTreeMap<i32, TickInfo>,
+
+       /// The state of every position, keyed by the id of its non-fungible token.
+       positions: HashMap<NonFungibleLocalId, Position>,
+
+       /// The resource address of the non-fungible tokens representing the positions.
+       position_address: ResourceAddress,
+
+       /// Admin badge used to mint and burn the position non-fungible tokens
+       position_admin_badge: Vault,
+
+       /// The id of the next position non-fungible token to be minted.
+       position_counter: u64,
+
+       /// When the pool is paused no swaps can be made and no positions can be created,
+       /// but positions can still be removed and their fees collected
+       paused: bool
+    }
+
+    impl ConcentratedPool {
+        /// Creates a new concentrated pool based on two resources addresses, fee amount, initial price and tick spacing
+        /// validations include:
+        ///  - Check the two resource addresses are not the same
+        ///  - Check resources are both fungible
+        ///  - Check fee amount set is decimal between 0 and less than 100
+        ///  - Check the initial price is positive and within the tick bounds
+        ///  - Check the tick spacing is positive
+        /// Note: the pool starts without any liquidity, which is added by creating positions through `add_liquidity`
+        pub fn instantiate_pool(
+            token1_address: ResourceAddress,
+            token2_address: ResourceAddress,
+            fee_to_pool: Decimal,
+            initial_price: Decimal,
+            tick_spacing: i32) -> ConcentratedPoolComponent {
+            // Check token addresses are not the same
+            assert_ne!(
+                token1_address, token2_address,
+                "[Concentrated Pool Creation]: Liquidity pools may only be created between two different tokens."
+            );
+
+            // Check resources neither is Non-Fungible
+            assert_eq!(
+                borrow_resource_manager!(token1_address).resource_type().is_fungible(), true,
+                "[Concentrated Pool Creation]: Both assets must be fungible."
+            );
+            assert_eq!(
+                borrow_resource_manager!(token2_address).resource_type().is_fungible(), true,
+                "[Concentrated Pool Creation]: Both assets must be fungible."
+            );
+
+            // Check fee amount set is decimal between 0 and less than 100
+            assert!(
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")),
+                "[Concentrated Pool Creation]: Fee must be between 0 and 100"
+            );
+
+            // Check the tick spacing is positive
+            assert!(tick_spacing > 0, "[Concentrated Pool Creation]: Tick spacing must be positive.");
+
+            // The initial price is given as the price of the first token passed in terms of the second one, so it is
+            // flipped if the sorting of the addresses swapped their order.
+            assert!(initial_price > Decimal::zero(), "[Concentrated Pool Creation]: Initial price must be positive.");
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(token1_address, token2_address);
+            let price: Decimal = if addresses.0 == token1_address { initial_price } else { Decimal::one() / initial_price };
+            let sqrt_price: Decimal = sqrt(price);
+            assert!(
+                (sqrt_price >= sqrt_price_at_tick(-MAX_TICK)) & (sqrt_price <= sqrt_price_at_tick(MAX_TICK)),
+                "[Concentrated Pool Creation]: Initial price is out of bounds."
+            );
+
+            let lp_id: String = format!("{:?}-{:?}", addresses.0, addresses.1);
+            let pair_name: String = address_pair_symbol(addresses.0, addresses.1);
+
+            info!(
+                "[Concentrated Pool Creation]: Creating new pool between tokens: {}, of name: {}, Price: {}",
+                lp_id, pair_name, price
+            );
+
+            let mut vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            vaults.insert(addresses.0, Vault::new(addresses.0));
+            vaults.insert(addresses.1, Vault::new(addresses.1));
+            let mut protocol_vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            protocol_vaults.insert(addresses.0, Vault::new(addresses.0));
+            protocol_vaults.insert(addresses.1, Vault::new(addresses.1));
+
+            // Create Admin badge to give authority for minting and burning the position non-fungible tokens
+            let position_admin_badge: Bucket = ResourceBuilder::new_fungible()
+            .divisibility(DIVISIBILITY_NONE)
+            .metadata("name", "Position Admin Badge")
+            .metadata("symbol", "PAB")
+            .metadata("description", "This is an admin badge that has the authority to mint and burn position tokens")
+            .metadata("lp_id", format!("{}", lp_id))
+            .mint_initial_supply(1);
+
+            // Creating the non-fungible resource of the positions
+            let position_address: ResourceAddress = ResourceBuilder::new_integer_non_fungible()
+                .metadata("name", format!("{} Concentrated LP Position", pair_name))
+                .metadata("symbol", "CLP")
+                .metadata("description", "A non-fungible token representing a liquidity position within a price range")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(position_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(position_admin_badge.resource_address())), LOCKED)
+                .create_with_no_initial_supply();
+
+            // Creating the liquidity pool component and instantiating it
+            let liquidity_pool = Self {
+                vaults: vaults,
+                addresses: addresses,
+                fee_to_pool: fee_to_pool,
+                protocol_fee: Decimal::zero(),
+                protocol_vaults: protocol_vaults,
+                tick_spacing: tick_spacing,
+                sqrt_price: sqrt_price,
+                current_tick: tick_at_sqrt_price(sqrt_price),
+                liquidity: Decimal::zero(),
+                fee_growth_global: (Decimal::zero(), Decimal::zero()),
+                ticks: BTreeMap::new(),
+                positions: HashMap::new(),
+                position_address: position_address,
+                position_admin_badge: Vault::with_bucket(position_admin_badge),
+                position_counter: 1,
+                paused: false
+            }
+            .instantiate()
+            // .globalize() NOTE: comment out if running manifests under `./manifests/pool` and using setup_pool_test.sh
+            ;
+
+            return liquidity_pool;
+        }
+
+        /// Checks if the given address belongs to this pool or not.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `bool` - A boolean of whether the address belongs to this pool or not.
+        pub fn belongs_to_pool(
+            &self,
+            address: ResourceAddress
+        ) -> bool {
+            return self.vaults.contains_key(&address);
+        }
+
+        /// Asserts that the given address belongs to the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        pub fn assert_belongs_to_pool(
+            &self,
+            address: ResourceAddress,
+            label: String
+        ) {
+            assert!(
+                self.belongs_to_pool(address),
+                "[{}]: The provided resource address does not belong to the pool.",
+                label
+            );
+        }
+
+        /// Gets the name of the given liquidity pool from the symbols of the two tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// `String` - A string of the pair symbol
+        pub fn name(&self) -> String {
+            return address_pair_symbol(self.addresses.0, self.addresses.1);
+        }
+
+        /// Gets the resource address of the non-fungible tokens representing the positions of this pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the position non-fungible tokens.
+        pub fn position_address(&self) -> ResourceAddress {
+            return self.position_address;
+        }
+
+        /// Gets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the share of the swap fee paid to the protocol.
+        pub fn protocol_fee(&self) -> Decimal {
+            return self.protocol_fee;
+        }
+
+        /// Sets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// This method performs a number of checks before the protocol fee is set:
+        /// 
+        /// * **Check 1:** Checks that the protocol fee is a decimal between 0 and 100.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `protocol_fee` (Decimal) - The percentage of the swap fee to divert to the protocol.
+        pub fn set_protocol_fee(
+            &mut self,
+            protocol_fee: Decimal
+        ) {
+            assert!(
+                (protocol_fee >= Decimal::zero()) & (protocol_fee <= dec!("100")), 
+                "[Concentrated Set Protocol Fee]: Protocol fee must be between 0 and 100"
+            );
+            info!("[Concentrated Set Protocol Fee]: Protocol fee set from {} to {}", self.protocol_fee, protocol_fee);
+
+            self.protocol_fee = protocol_fee;
+        }
+
+        /// Withdraws all of the protocol fees collected by this concentrated pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the protocol fees of each of the two tokens.
+        pub fn collect_protocol_fees(&mut self) -> Vec<Bucket> {
+            return self.protocol_vaults
+                .values_mut()
+                .map(|vault| vault.take_all())
+                .collect::<Vec<Bucket>>();
+        }
+
+        /// Gets whether this concentrated pool is paused or not.
+        /// 
+        /// # Returns:
+        /// 
+        /// `bool` - A boolean of whether the pool is paused.
+        pub fn paused(&self) -> bool {
+            return self.paused;
+        }
+
+        /// Pauses or unpauses this concentrated pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `paused` (bool) - Whether the pool should be paused.
+        pub fn set_paused(
+            &mut self,
+            paused: bool
+        ) {
+            info!("[Concentrated Set Paused]: Pool paused set from {} to {}", self.paused, paused);
+            self.paused = paused;
+        }
+
+        /// Asserts that this concentrated pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_paused(
+            &self,
+            label: String
+        ) {
+            assert!(!self.paused, "[{}]: The concentrated pool is paused.", label);
+        }
+
+        /// Gets the current state of the price of this pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The current price of the first sorted token in terms of the second one.
+        /// * `i32` - The current tick.
+        /// * `Decimal` - The liquidity active at the current price.
+        pub fn price_state(&self) -> (Decimal, i32, Decimal) {
+            return (self.sqrt_price * self.sqrt_price, self.current_tick, self.liquidity);
+        }
+
+        /// Gets the state of the given position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position_id` (NonFungibleLocalId) - The id of the non-fungible token of the position.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Position` - The state of the position, where `tokens_owed` only includes the fees as of the last time
+        /// that the position was updated.
+        pub fn position(
+            &self,
+            position_id: NonFungibleLocalId
+        ) -> Position {
+            assert!(
+                self.positions.contains_key(&position_id),
+                "[Concentrated Position]: No position exists with the given id."
+            );
+            return self.positions[&position_id].clone();
+        }
+
+        /// Calculates the spot price of the given token in terms of the other token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the token to get the price of.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of the other token that one input token is worth at the current price.
+        pub fn spot_price(
+            &self,
+            input_resource_address: ResourceAddress
+        ) -> Decimal {
+            self.assert_belongs_to_pool(input_resource_address, String::from("Concentrated Spot Price"));
+
+            let price: Decimal = self.sqrt_price * self.sqrt_price;
+            return if input_resource_address == self.addresses.0 { price } else { Decimal::one() / price };
+        }
+
+        /// Asserts that the given ticks are valid bounds for a position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `lower_tick` (i32) - The lower tick of the position.
+        /// * `upper_tick` (i32) - The upper tick of the position.
+        fn assert_valid_ticks(
+            &self,
+            lower_tick: i32,
+            upper_tick: i32
+        ) {
+            assert!(lower_tick < upper_tick, "[Concentrated Ticks]: The lower tick must be less than the upper tick.");
+            assert!(
+                (lower_tick >= -MAX_TICK) & (upper_tick <= MAX_TICK),
+                "[Concentrated Ticks]: The ticks must be between -{} and {}.",
+                MAX_TICK, MAX_TICK
+            );
+            assert!(
+                (lower_tick % self.tick_spacing == 0) & (upper_tick % self.tick_spacing == 0),
+                "[Concentrated Ticks]: The ticks must be multiples of the tick spacing {}.",
+                self.tick_spacing
+            );
+        }
+
+        /// Calculates the fee growth per unit of liquidity inside the given price range.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `lower_tick` (i32) - The lower tick of the range.
+        /// * `upper_tick` (i32) - The upper tick of the range.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `(Decimal, Decimal)` - The fee growth of the first and second token inside the range.
+        /// 
+        /// # Note:
+        /// 
+        /// The fee growth inside a range is the global fee growth minus the fee growth below the lower tick and above
+        /// the upper tick. Every tick stores the fee growth on the side of it away from the current price, so the fee
+        /// growth below the lower tick is its outside value when the current price is above it, or the global value
+        /// minus its outside value otherwise, and the same goes for the upper tick.
+        fn fee_growth_inside(
+            &self,
+            lower_tick: i32,
+            upper_tick: i32
+        ) -> (Decimal, Decimal) {
+            let lower: &TickInfo = &self.ticks[&lower_tick];
+            let upper: &TickInfo = &self.ticks[&upper_tick];
+            let global: (Decimal, Decimal) = self.fee_growth_global;
+
+            let below: (Decimal, Decimal) = if self.current_tick >= lower_tick {
+                lower.fee_growth_outside
+            } else {
+                (global.0 - lower.fee_growth_outside.0, global.1 - lower.fee_growth_outside.1)
+            };
+            let above: (Decimal, Decimal) = if self.current_tick < upper_tick {
+                upper.fee_growth_outside
+            } else {
+                (global.0 - upper.fee_growth_outside.0, global.1 - upper.fee_growth_outside.1)
+            };
+
+            return (global.0 - below.0 - above.0, global.1 - below.1 - above.1);
+        }
+
+        /// Adds the given liquidity delta to a tick, initializing the tick if it is not yet used by any position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tick` (i32) - The tick to update.
+        /// * `liquidity_delta` (Decimal) - The liquidity being added (positive) or removed (negative).
+        /// * `is_upper` (bool) - Whether the tick is the upper bound of the position being updated.
+        fn update_tick(
+            &mut self,
+            tick: i32,
+            liquidity_delta: Decimal,
+            is_upper: bool
+        ) {
+            // A newly initialized tick assumes that all of the fees so far were earned below it, which is the side of
+            // the current price if the current price is above the tick.
+            if !self.ticks.contains_key(&tick) {
+                let fee_growth_outside: (Decimal, Decimal) = if self.current_tick >= tick {
+                    self.fee_growth_global
+                } else {
+                    (Decimal::zero(), Decimal::zero())
+                };
+                self.ticks.insert(tick, TickInfo {
+                    liquidity_gross: Decimal::zero(),
+                    liquidity_net: Decimal::zero(),
+                    fee_growth_outside: fee_growth_outside
+                });
+            }
+
+            let tick_info: &mut TickInfo = self.ticks.get_mut(&tick).unwrap();
+            tick_info.liquidity_gross = tick_info.liquidity_gross + liquidity_delta;
+            tick_info.liquidity_net = if is_upper {
+                tick_info.liquidity_net - liquidity_delta
+            } else {
+                tick_info.liquidity_net + liquidity_delta
+            };
+
+            // Ticks that are no longer used by any position are cleared so that swaps do not stop at them.
+            if tick_info.liquidity_gross == Decimal::zero() {
+                self.ticks.remove(&tick);
+            }
+        }
+
+        /// Moves the fees accrued by a position since its last update into its `tokens_owed`.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position_id` (&NonFungibleLocalId) - The id of the non-fungible token of the position.
+        fn update_position_fees(
+            &mut self,
+            position_id: &NonFungibleLocalId
+        ) {
+            let position: Position = self.positions[position_id].clone();
+            let fee_growth_inside: (Decimal, Decimal) = self.fee_growth_inside(position.lower_tick, position.upper_tick);
+
+            let position: &mut Position = self.positions.get_mut(position_id).unwrap();
+            position.tokens_owed = (
+                position.tokens_owed.0 + position.liquidity * (fee_growth_inside.0 - position.fee_growth_inside_last.0),
+                position.tokens_owed.1 + position.liquidity * (fee_growth_inside.1 - position.fee_growth_inside_last.1)
+            );
+            position.fee_growth_inside_last = fee_growth_inside;
+        }
+
+        /// Walks a swap of the given amount of input tokens along the ticks without changing any state.
+        /// 
+        /// Within each range between two initialized ticks, the pool behaves like `x * y = k` with the active
+        /// liquidity. The swap moves the price towards the next initialized tick in the direction of the swap. If the
+        /// input is enough to reach the tick, the tick is crossed, which changes the active liquidity by the tick's
+        /// `liquidity_net`, and the swap continues into the next range. Otherwise the swap ends inside the range.
+        /// 
+        /// Every step is rounded in favour of the pool by `rounding_margin`: the input needed to reach a tick is 
+        /// rounded up, the movement of the price inside of a range is rounded towards the starting price, and the 
+        /// output is rounded down. This keeps swaps from chipping away at the margin that the positions rely on.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `zero_for_one` (bool) - Whether the first token is being swapped for the second, which moves the price
+        /// down, or the other way around, which moves the price up.
+        /// * `input_amount` (Decimal) - The amount of input tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `SwapComputation` - The state of the pool after the swap along with the amount of output tokens.
+        fn compute_swap(
+            &self,
+            zero_for_one: bool,
+            input_amount: Decimal
+        ) -> SwapComputation {
+            let mut computation: SwapComputation = SwapComputation {
+                amount_out: Decimal::zero(),
+                amount_remaining: input_amount,
+                sqrt_price: self.sqrt_price,
+                current_tick: self.current_tick,
+                liquidity: self.liquidity,
+                fee_growth_global_input: if zero_for_one { self.fee_growth_global.0 } else { self.fee_growth_global.1 },
+                protocol_amount: Decimal::zero(),
+                crossed_ticks: Vec::new()
+            };
+
+            computation.walk_ticks(&self.ticks, self.fee_to_pool, self.protocol_fee, zero_for_one);
+
+            return computation;
+        }
+
+        /// This method calculates the amount of output tokens that would be received for a given amount of an input
+        /// token by walking the swap along the ticks of the pool.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that the provided resource address belongs to this liquidity pool.
+        /// * **Check 2:** Checks that there is enough liquidity for the whole input amount to be swapped.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to calculate the output for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The output amount for the given input.
+        pub fn calculate_output_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            input_amount: Decimal
+        ) -> Decimal {
+            self.assert_belongs_to_pool(input_resource_address, String::from("Concentrated Calculate Output"));
+
+            let computation: SwapComputation = self.compute_swap(input_resource_address == self.addresses.0, input_amount);
+            assert!(
+                computation.amount_remaining == Decimal::zero(),
+                "[Concentrated Calculate Output]: Not enough liquidity for the given input amount."
+            );
+            return computation.amount_out;
+        }
+
+        /// Creates a new position providing liquidity between the given ticks.
+        /// 
+        /// The largest liquidity that the two buckets can provide over the range at the current price is calculated
+        /// and only the amounts of the tokens needed for it are taken, while the rest is returned. A range entirely
+        /// above the current price only takes the first token and a range entirely below it only takes the second one.
+        /// 
+        /// This method performs a number of checks before the position is created:
+        /// 
+        /// * **Check 1:** Checks that the concentrated pool is not paused.
+        /// * **Check 2:** Checks that the buckets passed are of the two different tokens of this liquidity pool.
+        /// * **Check 3:** Checks that the ticks are valid bounds for a position.
+        /// * **Check 4:** Checks that the buckets provide a positive amount of liquidity.
+        /// * **Check 5:** Checks that the buckets cover the amounts needed for the liquidity after rounding.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `lower_tick` (i32) - The lower tick of the price range of the position.
+        /// * `upper_tick` (i32) - The upper tick of the price range of the position.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the non-fungible token representing the position.
+        pub fn add_liquidity(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            lower_tick: i32,
+            upper_tick: i32
+        ) -> (Bucket, Bucket, Bucket) {
+            // Checking that the pool is not paused and that the tokens belong to this liquidity pool.
+            self.assert_not_paused(String::from("Concentrated Add Liquidity"));
+            self.assert_belongs_to_pool(token1.resource_address(), String::from("Concentrated Add Liquidity"));
+            self.assert_belongs_to_pool(token2.resource_address(), String::from("Concentrated Add Liquidity"));
+            assert_ne!(
+                token1.resource_address(), token2.resource_address(),
+                "[Concentrated Add Liquidity]: The two buckets must be of different tokens."
+            );
+            self.assert_valid_ticks(lower_tick, upper_tick);
+
+            // Calculating the liquidity of the position and the amounts of the tokens that it needs. The liquidity is 
+            // rounded down and the amounts are rounded up so that the pool always holds enough to pay out every 
+            // position, while the amounts still stay within what the buckets can provide.
+            let (mut bucket1, mut bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let sqrt_price_lower: Decimal = sqrt_price_at_tick(lower_tick);
+            let sqrt_price_upper: Decimal = sqrt_price_at_tick(upper_tick);
+            let max_liquidity: Decimal = liquidity_for_amounts(
+                self.sqrt_price, sqrt_price_lower, sqrt_price_upper, bucket1.amount(), bucket2.amount()
+            );
+            let liquidity: Decimal = max_liquidity - max_liquidity * dec!("2") * rounding_margin();
+            assert!(liquidity > Decimal::zero(), "[Concentrated Add Liquidity]: The position must provide liquidity.");
+
+            let (exact_amount1, exact_amount2): (Decimal, Decimal) = amounts_for_liquidity(
+                self.sqrt_price, sqrt_price_lower, sqrt_price_upper, liquidity
+            );
+            let amount1: Decimal = exact_amount1 + exact_amount1 * rounding_margin();
+            let amount2: Decimal = exact_amount2 + exact_amount2 * rounding_margin();
+            assert!(
+                (bucket1.amount() >= amount1) & (bucket2.amount() >= amount2),
+                "[Concentrated Add Liquidity]: The buckets do not cover the amounts of {} and {} needed.",
+                amount1, amount2
+            );
+            info!(
+                "[Concentrated Add Liquidity]: Adding liquidity {} between ticks {} and {} from amounts {}, {}",
+                liquidity, lower_tick, upper_tick, amount1, amount2
+            );
+
+            // Depositing the tokens
+            self.vaults.get_mut(&self.addresses.0).unwrap().put(bucket1.take(amount1));
+            self.vaults.get_mut(&self.addresses.1).unwrap().put(bucket2.take(amount2));
+
+            // Updating the ticks, and the active liquidity if the range includes the current price
+            self.update_tick(lower_tick, liquidity, false);
+            self.update_tick(upper_tick, liquidity, true);
+            if (self.current_tick >= lower_tick) & (self.current_tick < upper_tick) {
+                self.liquidity = self.liquidity + liquidity;
+            }
+
+            // Creating the position and minting its non-fungible token
+            let position_id: NonFungibleLocalId = NonFungibleLocalId::integer(self.position_counter);
+            self.position_counter = self.position_counter + 1;
+            self.positions.insert(position_id.clone(), Position {
+                lower_tick: lower_tick,
+                upper_tick: upper_tick,
+                liquidity: liquidity,
+                fee_growth_inside_last: self.fee_growth_inside(lower_tick, upper_tick),
+                tokens_owed: (Decimal::zero(), Decimal::zero())
+            });
+
+            let position_manager: ResourceManager = borrow_resource_manager!(self.position_address);
+            let position: Bucket = self.position_admin_badge.authorize(|| {
+                position_manager.mint_non_fungible(&position_id, PositionData {
+                    lower_tick: lower_tick,
+                    upper_tick: upper_tick
+                })
+            });
+
+            return (bucket1, bucket2, position);
+        }
+
+        /// Asserts that the given bucket holds exactly one position of this pool and returns its id.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (&Bucket) - A bucket of the non-fungible token of the position.
+        /// * `label` (String) - The label of the method that called this method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `NonFungibleLocalId` - The id of the position.
+        fn position_id(
+            &self,
+            position: &Bucket,
+            label: String
+        ) -> NonFungibleLocalId {
+            assert_eq!(
+                position.resource_address(), self.position_address,
+                "[{}]: The position given does not belong to this liquidity pool.",
+                label
+            );
+            assert_eq!(position.amount(), Decimal::one(), "[{}]: Exactly one position must be given.", label);
+
+            return position.non_fungible_local_id();
+        }
+
+        /// Collects the fees accrued by a position without removing its liquidity.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (Bucket) - A bucket of the non-fungible token of the position.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the non-fungible token of the position, which is given back.
+        /// * `Bucket` - A bucket of the fees of the first sorted token.
+        /// * `Bucket` - A bucket of the fees of the second sorted token.
+        pub fn collect_fees(
+            &mut self,
+            position: Bucket
+        ) -> (Bucket, Bucket, Bucket) {
+            let position_id: NonFungibleLocalId = self.position_id(&position, String::from("Concentrated Collect Fees"));
+            self.update_position_fees(&position_id);
+
+            let tokens_owed: (Decimal, Decimal) = self.positions[&position_id].tokens_owed;
+            self.positions.get_mut(&position_id).unwrap().tokens_owed = (Decimal::zero(), Decimal::zero());
+            info!("[Concentrated Collect Fees]: Collecting fees of {}, {}", tokens_owed.0, tokens_owed.1);
+
+            let fees1: Bucket = self.vaults.get_mut(&self.addresses.0).unwrap().take(tokens_owed.0);
+            let fees2: Bucket = self.vaults.get_mut(&self.addresses.1).unwrap().take(tokens_owed.1);
+            return (position, fees1, fees2);
+        }
+
+        /// Removes all of the liquidity of a position along with its fees and burns the position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (Bucket) - A bucket of the non-fungible token of the position.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A Bucket of the liquidity and fees of the first sorted token.
+        /// * `Bucket` - A Bucket of the liquidity and fees of the second sorted token.
+        pub fn remove_liquidity(
+            &mut self,
+            position: Bucket
+        ) -> (Bucket, Bucket) {
+            let position_id: NonFungibleLocalId = self.position_id(&position, String::from("Concentrated Remove Liquidity"));
+            self.update_position_fees(&position_id);
+            let position_state: Position = self.positions.remove(&position_id).unwrap();
+
+            // Calculating the amounts that the liquidity of the position is worth at the current price
+            let (amount1, amount2): (Decimal, Decimal) = amounts_for_liquidity(
+                self.sqrt_price,
+                sqrt_price_at_tick(position_state.lower_tick),
+                sqrt_price_at_tick(position_state.upper_tick),
+                position_state.liquidity
+            );
+            info!(
+                "[Concentrated Remove Liquidity]: Removing liquidity {} worth {}, {} with fees {}, {}",
+                position_state.liquidity, amount1, amount2, position_state.tokens_owed.0, position_state.tokens_owed.1
+            );
+
+            // Updating the ticks, and the active liquidity if the range includes the current price
+            if (self.current_tick >= position_state.lower_tick) & (self.current_tick < position_state.upper_tick) {
+                self.liquidity = self.liquidity - position_state.liquidity;
+            }
+            self.update_tick(position_state.lower_tick, Decimal::zero() - position_state.liquidity, false);
+            self.update_tick(position_state.upper_tick, Decimal::zero() - position_state.liquidity, true);
+
+            // Burning the position and withdrawing the tokens owed to it
+            self.position_admin_badge.authorize(|| {
+                position.burn();
+            });
+
+            // The deposits of the positions are rounded in favour of the pool, so the vaults always cover what is owed.
+            // A shortfall would mean that the accounting of the pool is wrong, so it fails rather than underpaying.
+            let bucket1: Bucket = self.vaults.get_mut(&self.addresses.0).unwrap()
+                .take(amount1 + position_state.tokens_owed.0);
+            let bucket2: Bucket = self.vaults.get_mut(&self.addresses.1).unwrap()
+                .take(amount2 + position_state.tokens_owed.1);
+            return (bucket1, bucket2);
+        }
+
+        /// Performs the swap of tokens, crossing ticks as needed, and takes the pool fee in the process
+        /// 
+        /// The fee of every part of the swap goes to the positions that are active in the range where that part of the
+        /// swap takes place, in proportion to their liquidity, less the share of the fee set by `protocol_fee` which is
+        /// diverted into the protocol vaults.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the concentrated pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// * **Check 3:** Checks that there is enough liquidity for the whole input amount to be swapped.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap(
+            &mut self,
+            mut tokens: Bucket
+        ) -> Bucket {
+            self.assert_not_paused(String::from("Concentrated Swap"));
+            self.assert_belongs_to_pool(tokens.resource_address(), String::from("Concentrated Swap"));
+
+            let zero_for_one: bool = tokens.resource_address() == self.addresses.0;
+            let computation: SwapComputation = self.compute_swap(zero_for_one, tokens.amount());
+            assert!(
+                computation.amount_remaining == Decimal::zero(),
+                "[Concentrated Swap]: Not enough liquidity for the given input amount."
+            );
+            info!(
+                "[Concentrated Swap]: output amount is : {}, ticks crossed: {}",
+                computation.amount_out, computation.crossed_ticks.len()
+            );
+
+            // Flipping the fee growth outside of every crossed tick using the global fee growth at the moment that it
+            // was crossed. The fee growth of the output token does not change during the swap.
+            for (tick, fee_growth_global_input) in computation.crossed_ticks.iter() {
+                let fee_growth_global: (Decimal, Decimal) = if zero_for_one {
+                    (*fee_growth_global_input, self.fee_growth_global.1)
+                } else {
+                    (self.fee_growth_global.0, *fee_growth_global_input)
+                };
+                let tick_info: &mut TickInfo = self.ticks.get_mut(tick).unwrap();
+                tick_info.fee_growth_outside = (
+                    fee_growth_global.0 - tick_info.fee_growth_outside.0,
+                    fee_growth_global.1 - tick_info.fee_growth_outside.1
+                );
+            }
+
+            // Updating the price state of the pool
+            self.sqrt_price = computation.sqrt_price;
+            self.current_tick = computation.current_tick;
+            self.liquidity = computation.liquidity;
+            if zero_for_one {
+                self.fee_growth_global.0 = computation.fee_growth_global_input;
+            } else {
+                self.fee_growth_global.1 = computation.fee_growth_global_input;
+            }
+
+            // Depositing the input tokens, less the protocol's share of the fee, and returning a bucket of the output 
+            // tokens.
+            let output_resource_address: ResourceAddress = if zero_for_one { self.addresses.1 } else { self.addresses.0 };
+            if computation.protocol_amount > Decimal::zero() {
+                info!(
+                    "[Concentrated Protocol Fee]: Taking {} of {:?} as protocol fee", 
+                    computation.protocol_amount, tokens.resource_address()
+                );
+                self.protocol_vaults.get_mut(&tokens.resource_address()).unwrap()
+                    .put(tokens.take(computation.protocol_amount));
+            }
+            self.vaults.get_mut(&tokens.resource_address()).unwrap().put(tokens);
+            return self.vaults.get_mut(&output_resource_address).unwrap().take(computation.amount_out);
+        }
+
+        /// Swaps all of the given tokens for the other token.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the concentrated pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `min_amount_out` (Decimal) - The minimum amount of tokens that the caller is willing to accept before the
+        /// method fails.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap_exact_tokens_for_tokens(
+            &mut self,
+            tokens: Bucket,
+            min_amount_out: Decimal
+        ) -> Bucket {
+            self.assert_not_paused(String::from("Concentrated Swap Exact"));
+            let output_tokens: Bucket = self.swap(tokens);
+            assert!(output_tokens.amount() >= min_amount_out, "[Concentrated Swap Exact]: min_amount_out not satisfied.");
+
+            return output_tokens;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The ticks of two positions: one with a liquidity of 1000 over `[-600, 600]` and one with a liquidity of 2000 
+    /// over `[600, 1200]`.
+    fn two_position_ticks() -> BTreeMap<i32, TickInfo> {
+        let mut ticks: BTreeMap<i32, TickInfo> = BTreeMap::new();
+        for (tick, liquidity_gross, liquidity_net) in [
+            (-600, dec!("1000"), dec!("1000")),
+            (600, dec!("3000"), dec!("1000")),
+            (1200, dec!("2000"), dec!("-2000"))
+        ] {
+            ticks.insert(tick, TickInfo {
+                liquidity_gross: liquidity_gross,
+                liquidity_net: liquidity_net,
+                fee_growth_outside: (Decimal::zero(), Decimal::zero())
+            });
+        }
+        return ticks;
+    }
+
+    /// Walks a swap of the given amount from tick zero, where only the first position is active, with a fee of 0.3%.
+    fn walk_from_tick_zero(zero_for_one: bool, input_amount: Decimal) -> SwapComputation {
+        let mut computation: SwapComputation = SwapComputation {
+            amount_out: Decimal::zero(),
+            amount_remaining: input_amount,
+            sqrt_price: sqrt_price_at_tick(0),
+            current_tick: 0,
+            liquidity: dec!("1000"),
+            fee_growth_global_input: Decimal::zero(),
+            protocol_amount: Decimal::zero(),
+            crossed_ticks: Vec::new()
+        };
+        computation.walk_ticks(&two_position_ticks(), dec!("0.3"), Decimal::zero(), zero_for_one);
+        return computation;
+    }
+
+    #[test]
+    fn tick_at_sqrt_price_inverts_sqrt_price_at_tick() {
+        for tick in [-MAX_TICK, -1200, -1, 0, 1, 887, MAX_TICK] {
+            assert_eq!(tick_at_sqrt_price(sqrt_price_at_tick(tick)), tick);
+        }
+    }
+
+    #[test]
+    fn swap_inside_of_a_range_does_not_cross_any_tick() {
+        let computation: SwapComputation = walk_from_tick_zero(false, dec!("10"));
+        assert_eq!(computation.amount_remaining, Decimal::zero());
+        assert!(computation.crossed_ticks.is_empty());
+        assert_eq!(computation.liquidity, dec!("1000"));
+        assert_eq!(computation.current_tick, 198);
+        assert!((computation.amount_out - dec!("9.871580343950967275")).abs() < dec!("0.000000001"));
+    }
+
+    #[test]
+    fn swap_crossing_a_tick_activates_its_liquidity() {
+        let computation: SwapComputation = walk_from_tick_zero(false, dec!("50"));
+        assert_eq!(computation.amount_remaining, Decimal::zero());
+        assert_eq!(computation.crossed_ticks.len(), 1);
+        assert_eq!(computation.crossed_ticks[0].0, 600);
+        assert_eq!(computation.liquidity, dec!("2000"));
+        assert_eq!(computation.current_tick, 787);
+
+        // The first 30.54 of the input moves the price to tick 600 with a liquidity of 1000 and the rest moves it on 
+        // with a liquidity of 2000.
+        assert!((computation.amount_out - dec!("47.650155029409265237")).abs() < dec!("0.000000001"));
+    }
+
+    #[test]
+    fn swap_crossing_into_an_empty_range_stops_short() {
+        let computation: SwapComputation = walk_from_tick_zero(true, dec!("1000"));
+        assert!(computation.amount_remaining > Decimal::zero());
+        assert_eq!(computation.crossed_ticks.len(), 1);
+        assert_eq!(computation.crossed_ticks[0].0, -600);
+        assert_eq!(computation.liquidity, Decimal::zero());
+        assert_eq!(computation.current_tick, -601);
+        assert_eq!(computation.sqrt_price, sqrt_price_at_tick(-600));
+    }
+}
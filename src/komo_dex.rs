@@ -1,15 +1,311 @@
 use scrypto::prelude::*;
 use crate::liquidity_pool::pool::*;
+use crate::liquidity_pool::DynamicFeeConfig;
+use crate::stable_pool::stable_pool::*;
+use crate::stable_pool::{compute_d, compute_output_amount, compute_spot_price};
+use crate::weighted_pool::weighted_pool::*;
+use crate::concentrated_pool::concentrated_pool::*;
 use crate::utils::*;
 
+/// A quote of a swap through the DEX. Quotes are calculated from the current reserves of the liquidity pools and do not
+/// move any funds.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct Quote {
+    /// For exact input quotes, this is the amount of output tokens that would be received. For exact output quotes, 
+    /// this is the amount of input tokens that would be required.
+    pub amount: Decimal,
+
+    /// The price paid in the swap including the fee and the price impact, in output tokens per input token.
+    pub effective_price: Decimal,
+
+    /// The price of the input token at the current reserves, in output tokens per input token.
+    pub spot_price: Decimal,
+
+    /// The percentage by which the effective price is worse than the spot price.
+    pub price_impact: Decimal
+}
+
+/// Information about one of the liquidity pools on the DEX.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct PoolInfo {
+    /// The sorted resource addresses of the two tokens in the liquidity pool.
+    pub addresses: (ResourceAddress, ResourceAddress),
+
+    /// The resource address of the tracking tokens issued by the liquidity pool.
+    pub tracking_token_address: ResourceAddress,
+
+    /// The reserves of the two tokens in the liquidity pool, in the same order as `addresses`.
+    pub reserves: (Decimal, Decimal),
+
+    /// The percentage fee currently paid to the liquidity pool on swaps, which moves with volatility for pools with a
+    /// dynamic fee.
+    pub fee_to_pool: Decimal,
+
+    /// The name of the liquidity pool made up of the symbols of the two tokens.
+    pub name: String,
+
+    /// The amplification coefficient of the liquidity pool if it is a stable pool, or `None` if it is a constant 
+    /// product pool.
+    pub amplification: Option<Decimal>,
+
+    /// The fee tier of the liquidity pool in basis points if it is one of the fee tier pools of the pair, or `None` if
+    /// it is the main pool of the pair.
+    pub fee_tier: Option<u16>
+}
+
+/// The standard fee tiers, in basis points, that additional liquidity pools of an address pair may be created at. These
+/// are 0.05% for pairs of pegged tokens, 0.3% for most pairs, and 1% for pairs of volatile tokens.
+const FEE_TIERS: [u16; 3] = [5, 30, 100];
+
+/// The number of bisection steps taken by the search of `split_allocation` for the common marginal output. Every step 
+/// halves the interval being searched, so the marginal output found is within `2^-40` of the largest one of the routes.
+const SPLIT_SEARCH_ITERATIONS: u32 = 40;
+
+/// The number of bisection steps taken by `route_amount_at_marginal` for routes which go through a stable pool. This 
+/// search runs inside of every step of the search of `split_allocation`, so it is kept shorter than that one. The 
+/// amount found is within `2^-24` of the input amount of the exact one, and any input left over from the search is 
+/// swapped along one of the routes anyway.
+const ROUTE_SEARCH_ITERATIONS: u32 = 24;
+
+/// The largest number of Newton's method iterations taken when simulating a swap through a stable pool for 
+/// `split_allocation`. The iterations start from a point close to the answer and converge quadratically, so this only
+/// bounds the cost of the simulations for pools where they would not have converged anyway.
+const ROUTE_NEWTON_ITERATIONS: u32 = 32;
+
+/// A snapshot of the pool of one hop along a route. Swaps split across several routes are simulated on these instead
+/// of on the pools themselves, since the search for the best split simulates every route thousands of times.
+#[derive(Debug, Clone, Copy)]
+struct RouteHop {
+    /// The reserves of the input token of the hop.
+    input_reserve: Decimal,
+
+    /// The reserves of the output token of the hop.
+    output_reserve: Decimal,
+
+    /// The fee modifier of the pool, where `r = (100 - fee) / 100`.
+    fee_modifier: Decimal,
+
+    /// The amplification coefficient and the invariant `D` of the pool if it is a stable pool, or `None` if it is a 
+    /// constant product pool.
+    stable: Option<(Decimal, Decimal)>
+}
+
+impl RouteHop {
+    /// Calculates the output of the hop for the given input amount along with the marginal output, which is the 
+    /// derivative of the output with respect to the input at that amount.
+    /// 
+    /// # Arguments:
+    /// 
+    /// * `input_amount` (Decimal) - The amount of input tokens.
+    /// 
+    /// # Returns:
+    /// 
+    /// * `(Decimal, Decimal)` - The amount of output tokens and the marginal output.
+    /// 
+    /// # Note:
+    /// 
+    /// For a constant product pool the output is `rdx * y / (x + rdx)`, the same as `calculate_output_amount`, and the
+    /// marginal output is `rxy / (x + rdx)^2`. For a stable pool the marginal output is `r` times the spot price at 
+    /// the reserves after the swap.
+    fn output_and_marginal(
+        &self,
+        input_amount: Decimal
+    ) -> (Decimal, Decimal) {
+        let x: Decimal = self.input_reserve;
+        let y: Decimal = self.output_reserve;
+        let r: Decimal = self.fee_modifier;
+        return match self.stable {
+            None => {
+                let denominator: Decimal = x + r * input_amount;
+                if denominator == Decimal::zero() {
+                    return (Decimal::zero(), Decimal::zero());
+                }
+                ((input_amount * r * y) / denominator, r * x * y / denominator / denominator)
+            }
+            Some((amplification, d)) => {
+                let output_amount: Decimal = compute_output_amount(
+                    x, 
+                    y, 
+                    d, 
+                    r * input_amount, 
+                    amplification, 
+                    ROUTE_NEWTON_ITERATIONS
+                );
+                let marginal: Decimal = r * compute_spot_price(
+                    x + r * input_amount, 
+                    y - output_amount, 
+                    d, 
+                    amplification
+                );
+                (output_amount, marginal)
+            }
+        };
+    }
+}
+
+/// Simulates a swap along a route of hops and calculates its marginal output through the chain rule.
+/// 
+/// # Arguments:
+/// 
+/// * `hops` (&Vec<RouteHop>) - The snapshots of the pools along the route, in order.
+/// * `input_amount` (Decimal) - The amount of input tokens.
+/// 
+/// # Returns:
+/// 
+/// * `(Decimal, Decimal)` - The amount of output tokens of the route and its marginal output.
+fn route_output_and_marginal(hops: &Vec<RouteHop>, input_amount: Decimal) -> (Decimal, Decimal) {
+    let mut amount: Decimal = input_amount;
+    let mut marginal: Decimal = Decimal::one();
+    for hop in hops {
+        let (output_amount, hop_marginal): (Decimal, Decimal) = hop.output_and_marginal(amount);
+        amount = output_amount;
+        marginal = marginal * hop_marginal;
+    }
+    return (amount, marginal);
+}
+
+/// Reduces a route made up only of constant product pools to the curve of a single pool.
+/// 
+/// # Arguments:
+/// 
+/// * `hops` (&Vec<RouteHop>) - The snapshots of the pools along the route, in order.
+/// 
+/// # Returns:
+/// 
+/// * `Option<(Decimal, Decimal)>` - The coefficients `(a, c)` of the output of the route, `a * dx / (1 + c * dx)`, or
+/// `None` if the route goes through a stable pool.
+/// 
+/// # Note:
+/// 
+/// The output of a constant product pool, `rdx * y / (x + rdx)`, is already of this form with `a = ry / x` and 
+/// `c = r / x`. Feeding the output of the route so far into the next pool keeps it in this form, with 
+/// `a' = a * ry / x` and `c' = c + a * r / x`. A route through a pool with no reserves of its input token gives no
+/// marginal output at any amount, which is the curve with `a = 0`.
+fn constant_product_curve(hops: &Vec<RouteHop>) -> Option<(Decimal, Decimal)> {
+    let mut a: Decimal = Decimal::one();
+    let mut c: Decimal = Decimal::zero();
+    for hop in hops {
+        if hop.stable.is_some() {
+            return None;
+        }
+        if hop.input_reserve == Decimal::zero() {
+            return Some((Decimal::zero(), Decimal::zero()));
+        }
+        c = c + a * hop.fee_modifier / hop.input_reserve;
+        a = a * hop.fee_modifier * hop.output_reserve / hop.input_reserve;
+    }
+    return Some((a, c));
+}
+
+/// Finds the input amount at which the marginal output of a route falls to the given marginal output.
+/// 
+/// # Arguments:
+/// 
+/// * `hops` (&Vec<RouteHop>) - The snapshots of the pools along the route, in order.
+/// * `marginal` (Decimal) - The marginal output to find the input amount of.
+/// * `max_amount` (Decimal) - The largest input amount which may be returned.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The input amount, which is at most `max_amount`.
+/// 
+/// # Note:
+/// 
+/// For a route of constant product pools the marginal output is `a / (1 + c * dx)^2` in terms of the coefficients of
+/// `constant_product_curve`, which is inverted directly to `dx = (sqrt(a / marginal) - 1) / c`. For a route through a 
+/// stable pool there is no closed form, so the amount is found through `ROUTE_SEARCH_ITERATIONS` steps of bisection,
+/// since the marginal output only falls as the input grows.
+fn route_amount_at_marginal(hops: &Vec<RouteHop>, marginal: Decimal, max_amount: Decimal) -> Decimal {
+    if let Some((a, c)) = constant_product_curve(hops) {
+        if a <= marginal {
+            return Decimal::zero();
+        }
+        if marginal == Decimal::zero() {
+            return max_amount;
+        }
+        let amount: Decimal = (sqrt(a / marginal) - Decimal::one()) / c;
+        return if amount < max_amount { amount } else { max_amount };
+    }
+
+    if route_output_and_marginal(hops, Decimal::zero()).1 <= marginal {
+        return Decimal::zero();
+    }
+    if route_output_and_marginal(hops, max_amount).1 >= marginal {
+        return max_amount;
+    }
+
+    let mut low: Decimal = Decimal::zero();
+    let mut high: Decimal = max_amount;
+    for _ in 0..ROUTE_SEARCH_ITERATIONS {
+        let middle: Decimal = (low + high) / dec!("2");
+        if route_output_and_marginal(hops, middle).1 > marginal {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    return low;
+}
+
+/// Splits an input amount across routes which do not share any pools such that the total output is maximized.
+/// 
+/// # Arguments:
+/// 
+/// * `routes` (&Vec<Vec<RouteHop>>) - The snapshots of the pools along each of the routes.
+/// * `input_amount` (Decimal) - The total amount of input tokens to split.
+/// 
+/// # Returns:
+/// 
+/// * `Vec<Decimal>` - The amount of input tokens to swap along each of the routes, in the same order as `routes`.
+/// 
+/// # Note:
+/// 
+/// The output of every route is concave in its input, so the total output is at its maximum when the marginal outputs
+/// of all of the routes used are equal and no unused route has a larger marginal output. The common marginal output is
+/// found through bisection: the lower it is, the more input every route takes at it, so it is moved until the amounts 
+/// that the routes take add up to the input amount. The amounts may add up to slightly less than the input amount 
+/// because of the bisection, so the caller swaps whatever remains along one of the routes.
+/// 
+/// Every step of the search finds the amount of every route at the marginal output through `route_amount_at_marginal`.
+/// For routes of constant product pools this is a closed form, so the whole search costs `SPLIT_SEARCH_ITERATIONS` 
+/// times the number of pools along the routes. A route through a stable pool instead costs `ROUTE_SEARCH_ITERATIONS`
+/// simulations of the route per step, each of which solves the StableSwap invariant through at most 
+/// `ROUTE_NEWTON_ITERATIONS` iterations of Newton's method, so splits across such routes are considerably more 
+/// expensive.
+fn split_allocation(routes: &Vec<Vec<RouteHop>>, input_amount: Decimal) -> Vec<Decimal> {
+    let mut low: Decimal = Decimal::zero();
+    let mut high: Decimal = routes.iter()
+        .map(|hops| route_output_and_marginal(hops, Decimal::zero()).1)
+        .max()
+        .unwrap_or(Decimal::zero());
+    for _ in 0..SPLIT_SEARCH_ITERATIONS {
+        let middle: Decimal = (low + high) / dec!("2");
+        let total: Decimal = routes.iter()
+            .map(|hops| route_amount_at_marginal(hops, middle, input_amount))
+            .fold(Decimal::zero(), |total, amount| total + amount);
+        if total > input_amount {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    return routes.iter()
+        .map(|hops| route_amount_at_marginal(hops, high, input_amount))
+        .collect::<Vec<Decimal>>();
+}
+
 #[blueprint]
 mod komo_dex {
     /// implementation of an automated market maker decentralized exchange on the Radix ledger. The 
-    /// liquidity pools in this DEX use the constant market maker function `x * y = k` for the trading of tokens.
+    /// liquidity pools in this DEX use the constant market maker function `x * y = k` for the trading of tokens, 
+    /// while the stable pools use the StableSwap invariant for the trading of pegged tokens.
     /// 
-    /// The blueprint and components do not perform any kind of mathematics, calculation, or anything like that on 
-    /// their own. Instead, this component may be thought of as a registry of all of the liquidity pools and as 
-    /// a router which routes swaps and other method calls to the correct liquidity pool.
+    /// This component may be thought of as a registry of all of the liquidity pools and as a router which routes 
+    /// swaps and other method calls to the correct liquidity pool. The pricing of swaps is left to the liquidity 
+    /// pools themselves, with one exception: to split a swap across several routes, the router takes snapshots of 
+    /// the pools along each route and simulates swaps on them, using the constant product formula and the StableSwap
+    /// functions of the stable pools, to find the split which gives the most output.
     struct KomoDEX{
         /// This is a hashmap that maps a tuple of two addresses to a Scrypto component. This scrypto component is a 
         /// liquidity pool meaning that this hashmap maps a tuple of two addresses to a liquidity pool. This hashmap is
@@ -17,28 +313,154 @@ mod komo_dex {
         /// addresses does not exist in this hashmap it means that there does not exist a liquidity pool for it on the DEX
         liquidity_pools: HashMap<(ResourceAddress, ResourceAddress), PoolComponent>,
 
+        /// This is a hashmap that maps a tuple of two addresses to a stable pool in the same way that the 
+        /// `liquidity_pools` hashmap does for the constant product liquidity pools. An address pair may only have a
+        /// pool in one of these two hashmaps, so the router can always tell which pool a swap should go through.
+        stable_pools: HashMap<(ResourceAddress, ResourceAddress), StablePoolComponent>,
+
         /// That's quite the mouthful. This is a hashmap that is mainly used when liquidity providers are tying to 
         /// remove their portion of liquidity from the liquidity pool. This hashmap is used to find the address pair (
         /// and in turn the liquidity pool) associated with a given tracking token. If the resource address of a given
         /// tracking token does not exist as one of the keys to this hashmap, then this means that this tracking token
         /// does not belong to any of the liquidity pools in the DEX.
-        tracking_token_address_pair_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress)>
+        tracking_token_address_pair_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress)>,
+
+        /// This is a hashmap that maps a sorted set of addresses to a weighted pool. Weighted pools can hold more than
+        /// two tokens, so they can not be keyed by an address pair like the other pools. The set of addresses is always
+        /// sorted through `sort_address_set` so that the same tokens always map to the same key regardless of the 
+        /// order they are given in.
+        weighted_pools: HashMap<Vec<ResourceAddress>, WeightedPoolComponent>,
+
+        /// This hashmap is the weighted pool equivalent of the `tracking_token_address_pair_mapping`. It maps the 
+        /// resource address of the tracking tokens of a weighted pool to the sorted set of addresses of that pool.
+        weighted_tracking_token_mapping: HashMap<ResourceAddress, Vec<ResourceAddress>>,
+
+        /// This is a hashmap that maps a sorted address pair to a concentrated liquidity pool. Concentrated pools are 
+        /// kept apart from the other pools since their liquidity depends on the price ranges of their positions, so
+        /// they are traded through their own methods rather than the routing of the DEX.
+        concentrated_pools: HashMap<(ResourceAddress, ResourceAddress), ConcentratedPoolComponent>,
+
+        /// This hashmap maps the resource address of the position non-fungible tokens of a concentrated pool to the 
+        /// address pair of that pool. It is used to find the pool of a position when it is given back to the DEX.
+        position_address_pair_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress)>,
+
+        /// The resource address of the admin badge of the DEX. The governance methods of the DEX are protected by 
+        /// access rules which require this badge to be present.
+        admin_badge_address: ResourceAddress,
+
+        /// The percentage of the swap fee of the pools which is diverted to the protocol. This is set on every pool 
+        /// when it's created and whenever it's changed through `set_protocol_fee`.
+        protocol_fee: Decimal,
+
+        /// The account that the protocol fees are deposited into when they're collected. When this is not set the 
+        /// protocol fees are returned to the caller of `collect_protocol_fees` instead.
+        protocol_fee_recipient: Option<ComponentAddress>,
+
+        /// The address pairs of the pools which have been delisted by the admin. Delisted pools can not be traded 
+        /// through or have liquidity added to them, and are left out of routing, but liquidity providers are still 
+        /// able to remove their liquidity from them. Delisting a pair delists all of its pools, including its fee tier
+        /// pools and its concentrated pool.
+        delisted_pools: HashSet<(ResourceAddress, ResourceAddress)>,
+
+        /// The sorted sets of addresses of the weighted pools which have been delisted by the admin. These are kept 
+        /// apart from the `delisted_pools` since weighted pools are keyed by a set of addresses rather than a pair.
+        delisted_weighted_pools: HashSet<Vec<ResourceAddress>>,
+
+        /// When the DEX is paused no swaps can be made and no liquidity can be added through any of its pools. This is
+        /// an emergency stop on top of the pausing of individual liquidity pools. Removing liquidity is always allowed.
+        paused: bool,
+
+        /// This hashmap maps the resource address of the flash loan receipts of a liquidity pool to the address pair 
+        /// and the fee tier of that pool, where the fee tier is `None` for the main pool of the pair. It is used to 
+        /// find the pool that a flash loan should be repaid to.
+        flash_loan_receipt_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress, Option<u16>)>,
+
+        /// This hashmap maps the resource address of the flash swap receipts of a liquidity pool to the address pair 
+        /// and the fee tier of that pool in the same way as the `flash_loan_receipt_mapping`. It is used to find the 
+        /// pool that a flash swap should be settled with.
+        flash_swap_receipt_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress, Option<u16>)>,
+
+        /// This hashmap maps a sorted address pair and a fee tier in basis points to a liquidity pool charging that 
+        /// fee. These pools are in addition to the pool of the pair in `liquidity_pools`, which lets a pair have pools
+        /// at several of the standard `FEE_TIERS` so that liquidity providers can choose the fee they earn. The 
+        /// administration, oracle, and flash loan methods of the DEX take an optional fee tier to reach these pools, 
+        /// except for `set_pool_fee` and `set_dynamic_fee` since the fee of these pools is fixed at their tier, and 
+        /// `list_pools` lists them alongside the main pools. The multi-hop routing and the quotes go through the 
+        /// main pool of every pair, while `swap_fee_tier` and `swap_best_fee_tier` trade through these pools.
+        fee_tier_pools: HashMap<(ResourceAddress, ResourceAddress, u16), PoolComponent>,
+
+        /// This hashmap is the fee tier equivalent of the `tracking_token_address_pair_mapping`. It maps the resource
+        /// address of the tracking tokens and the liquidity receipts of a fee tier pool to the key of that pool.
+        fee_tier_tracking_token_mapping: HashMap<ResourceAddress, (ResourceAddress, ResourceAddress, u16)>
     }
 
     impl KomoDEX {
         /// Instantiates a new KomoDEX component. 
         /// 
+        /// An admin badge is minted along with the component which is required to call the governance methods of the
+        /// DEX. These are the methods to set and collect the protocol fees, set the recipient of the protocol fees, set
+        /// the fees of pools, delist pools, pause and unpause the pools or the whole DEX, 
+        /// configure the circuit breakers of the pools, grow the price oracles of the pools, and configure the dynamic 
+        /// fees of the pools.
+        /// 
         /// # Returns 
         /// 
         /// `Component` - A new the DEX component.
-        pub fn new() -> ComponentAddress {
+        /// `Bucket` - A bucket of the admin badge of the DEX.
+        pub fn new() -> (ComponentAddress, Bucket) {
+            // Creating the admin badge of the DEX
+            let admin_badge: Bucket = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_NONE)
+                .metadata("name", "KomoDEX Admin Badge")
+                .metadata("symbol", "KDAB")
+                .metadata("description", "This is an admin badge that has the authority to govern the DEX")
+                .mint_initial_supply(1);
+
+            // Protecting the governance methods of the DEX behind the admin badge
+            let access_rules: AccessRulesConfig = AccessRulesConfig::new()
+                .method("set_protocol_fee", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("collect_protocol_fees", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("set_protocol_fee_recipient", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("set_pool_fee", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("delist_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("delist_weighted_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("pause_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("unpause_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("pause_weighted_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("unpause_weighted_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("pause_concentrated_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("unpause_concentrated_pool", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("pause", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("unpause", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("set_circuit_breaker", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("grow_observations", rule!(require(admin_badge.resource_address())), LOCKED)
+                .method("set_dynamic_fee", rule!(require(admin_badge.resource_address())), LOCKED)
+                .default(rule!(allow_all), LOCKED);
+
             // The DEX AMM does not take any arguments 
-            return Self {
+            let component_address: ComponentAddress = Self {
                 liquidity_pools: HashMap::new(), 
-                tracking_token_address_pair_mapping: HashMap::new()
+                stable_pools: HashMap::new(),
+                tracking_token_address_pair_mapping: HashMap::new(),
+                weighted_pools: HashMap::new(),
+                weighted_tracking_token_mapping: HashMap::new(),
+                concentrated_pools: HashMap::new(),
+                position_address_pair_mapping: HashMap::new(),
+                admin_badge_address: admin_badge.resource_address(),
+                protocol_fee: Decimal::zero(),
+                protocol_fee_recipient: None,
+                delisted_pools: HashSet::new(),
+                delisted_weighted_pools: HashSet::new(),
+                paused: false,
+                flash_loan_receipt_mapping: HashMap::new(),
+                flash_swap_receipt_mapping: HashMap::new(),
+                fee_tier_pools: HashMap::new(),
+                fee_tier_tracking_token_mapping: HashMap::new()
             }
             .instantiate()
-            .globalize();
+            .globalize_with_access_rules(access_rules);
+
+            return (component_address, admin_badge);
         }
 
         /// Checks if a liquidity pool for the given pair of tokens exists or not.
@@ -56,10 +478,10 @@ mod komo_dex {
             address1: ResourceAddress,
             address2: ResourceAddress
         ) -> bool {
-            // Sorting the two addresses passed and then checking if the tuple of sorted addresses exists in the hashmap
+            // Sorting the two addresses passed and then checking if the tuple of sorted addresses exists in the hashmaps
             // of liquidity pools or not.
             let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
-            return self.liquidity_pools.contains_key(&sorted_addresses);
+            return self.liquidity_pools.contains_key(&sorted_addresses) | self.stable_pools.contains_key(&sorted_addresses);
         }
 
         /// Asserts that a liquidity pool for the given address pair exists on the DEX.
@@ -100,161 +522,93 @@ mod komo_dex {
             );
         }
 
-        /// Creates a new liquidity pool in the DEX.
-        /// 
-        /// This method is used to create a new liquidity pool between the two provided tokens on the DEX.
-        /// 
-        /// This method does a number of checks before a Liquidity Pool is created, these checks are:
-        /// 
-        /// * **Check 1:** Checks that there does not already exist a liquidity pool for the two given tokens.
-        /// 
-        /// The majority of the checking is done in the `new` function of the LiquidityPool where it checks to ensure 
-        /// that the buckets are not empty, tokens are not both the same, as well as other things. The checks done here
-        /// are just DEX checks to ensure that we don't create a liquidity pool for a token pair that already has a 
-        /// liquidity pool.
+        /// Asserts that the DEX is not paused.
         /// 
-        /// # Arguments: 
+        /// # Arguments:
         /// 
-        /// * `token1` (Bucket) - A bucket containing the amount of the first token used to initialize the pool.
-        /// * `token2` (Bucket) - A bucket containing the amount of the second token used to initialize the pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_paused(
+            &self,
+            label: String
+        ) {
+            assert!(!self.paused, "[{}]: The DEX is paused.", label);
+        }
+
+        /// Asserts that the pool of the given address pair has not been delisted.
         /// 
-        /// # Returns:
+        /// # Arguments:
         /// 
-        /// * `Bucket` - A bucket containing the tracking tokens issued to the creator of the liquidity pool.
-        pub fn new_liquidity_pool(
-            &mut self,
-            token1: Bucket,
-            token2: Bucket,
-            fee_to_pool: Decimal
-        ) -> Bucket {
-            // Checking if a liquidity pool already exists between these two tokens
-            self.assert_pool_doesnt_exists(
-                token1.resource_address(), token2.resource_address(), 
-                String::from("New Liquidity Pool")
-            );
-
-            // Sorting the two buckets according to their resource addresses and creating a liquidity pool from these
-            // two buckets.
-            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
-            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address()); 
-            let (pool, tracking_tokens): (PoolComponent, Bucket) = Pool::instantiate_pool(
-                bucket1, bucket2, fee_to_pool
-
-            );
-            
-            // Adding the liquidity pool to the hashmap of all liquidity pools
-            self.liquidity_pools.insert(
-                addresses,
-                pool
-            );
-
-            // Adding the resource address of the tracking tokens to the hashmap that maps the tracking tokens with 
-            // the address of their token pairs
-            self.tracking_token_address_pair_mapping.insert(
-                tracking_tokens.resource_address(),
-                addresses
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_pool_listed(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            label: String
+        ) {
+            assert!(
+                !self.delisted_pools.contains(&sort_addresses(address1, address2)), 
+                "[{}]: The pool of the given address pair has been delisted.", 
+                label
             );
-
-            // globalize the liquidity pool 
-            // TODO: other than test purposes why do I need this?
- 
-
-            // Returning the tracking tokens back to the caller of this method (the initial liquidity provider).
-            return tracking_tokens;
         }
 
-        /// Adds liquidity to a new or an already existing liquidity pool.
-        /// 
-        /// This method is used to add liquidity to a liquidity pool in the DEX. If a liquidity pool for the two tokens
-        /// passed already exists then liquidity would be directly added to it. However, if a pool doesn't exist, then a
-        /// new liquidity pool is created from the two buckets passed to this method.
+        /// Gets the constant product liquidity pool of the given address pair.
         /// 
         /// # Arguments:
         /// 
-        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
-        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `label` (String) - The label of the method that called this method.
         /// 
         /// # Returns:
         /// 
-        /// * `Bucket` - A bucket of the remaining tokens of the `token1` type.
-        /// * `Bucket` - A bucket of the remaining tokens of the `token2` type.
-        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
-        pub fn add_liquidity(
-            &mut self,
-            token1: Bucket,
-            token2: Bucket,
-            fee_to_pool: Decimal
-        ) -> (Option<Bucket>, Option<Bucket>, Bucket) {
-            // Sorting the two buckets of tokens passed to this method and getting the addresses of their resources.
-            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
-            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address()); 
-
-            // Attempting to get the liquidity pool component associated with the provided address pair.
-            let optional_liquidity_pool: Option<&PoolComponent> = self.liquidity_pools.get(&addresses);
-            match optional_liquidity_pool {
-                Some (liquidity_pool) => { // If it matches it means that the liquidity pool exists.
-                    info!("[DEX Add Liquidity]: Pool for {:?} already exists. Adding liquidity directly.", addresses);
-                    let returns: (Bucket, Bucket, Bucket) = liquidity_pool.add_liquidity(bucket1, bucket2);
-                    (Some(returns.0), Some(returns.1), returns.2)
-                }
-                None => { // If this matches then there does not exist a liquidity pool for this token pair
-                    // In here we are creating a new liquidity pool for this token pair since we failed to find an 
-                    // already existing liquidity pool. The return statement below might seem somewhat redundant in 
-                    // terms of the two empty buckets being returned, but this is done to allow for the add liquidity
-                    // method to be general and allow for the possibility of the liquidity pool not being there.
-                    info!("[DEX Add Liquidity]: Pool for {:?} doesn't exist. Creating a new one.", addresses);
-                    (None, None, self.new_liquidity_pool(bucket1, bucket2, fee_to_pool))
-                }
-            }
+        /// * `&PoolComponent` - The liquidity pool of the address pair.
+        fn liquidity_pool(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            label: String
+        ) -> &PoolComponent {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            assert!(
+                self.liquidity_pools.contains_key(&sorted_addresses), 
+                "[{}]: No liquidity pool exists for the given address pair.", 
+                label
+            );
+            return &self.liquidity_pools[&sorted_addresses];
         }
 
-        /// Removes liquidity from the appropriate liquidity pool in the DEX.
-        /// 
-        /// The main use of this method is to remove liquidity from one of the liquidity pools in the DEX and return 
-        /// back the liquidity provider's share of the liquidity pool. The first thing that this method does is that it
-        /// ensures that the tracking tokens are valid tracking tokens that actually belong to a liquidity pool. This 
-        /// method then finds the liquidity pool that issues the specified tracking tokens and removes the liquidity 
-        /// from it.
-        /// 
-        /// This method performs a number of checks before liquidity removed from the pool:
-        /// 
-        /// * **Check 1:** Checks to ensure that the provided tracking tokens are valid.
+        /// Gets the constant product liquidity pool of the given address pair at the given fee tier.
         /// 
         /// # Arguments:
         /// 
-        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to 
-        /// exchange for their share of the liquidity.
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `label` (String) - The label of the method that called this method.
         /// 
         /// # Returns:
         /// 
-        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first token.
-        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second token.
-        pub fn remove_liquidity(
-            &mut self,
-            tracking_tokens: Bucket
-        ) -> (Bucket, Bucket) {
-            // Check to make sure that the tracking tokens provided are indeed valid tracking tokens that belong to this
-            // DEX.
-            assert!(
-                self.tracking_token_address_pair_mapping.contains_key(&tracking_tokens.resource_address()),
-                "[DEX Remove Liquidity]: The tracking tokens given do not belong to this exchange."
-            );
-
-            // Getting the address pair associated with the resource address of the tracking tokens and then requesting
-            // the removal of liquidity from the liquidity pool
-            let addresses: (ResourceAddress, ResourceAddress) = self.tracking_token_address_pair_mapping[&tracking_tokens.resource_address()];
-            return self.liquidity_pools[&addresses].remove_liquidity(tracking_tokens);
+        /// * `&PoolComponent` - The liquidity pool of the address pair at the fee tier.
+        fn liquidity_pool_at_tier(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>,
+            label: String
+        ) -> &PoolComponent {
+            return match fee_tier {
+                Some(fee_tier) => &self.fee_tier_pools[
+                    &self.assert_fee_tier_pool_exists(address1, address2, fee_tier, label)
+                ],
+                None => self.liquidity_pool(address1, address2, label)
+            };
         }
 
-        /// Swaps the input tokens for tokens of the desired type.
-        /// 
-        /// This method is used to swap tokens for other tokens. This method first checks that there does exist a 
-        /// liquidity pool between the input and the output tokens. If a liquidity pool is found, then the swap goes
-        /// through.
-        /// 
-        /// This method performs a number of checks before the swap is performed:
-        /// 
-        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens.
+        /// Swaps the input tokens through the liquidity pool or the stable pool of the given address pair.
         /// 
         /// # Arguments:
         /// 
@@ -264,68 +618,974 @@ mod komo_dex {
         /// # Returns:
         /// 
         /// * `Bucket` - A bucket of the other tokens.
-        pub fn swap(
-            &mut self,
+        fn pool_swap(
+            &self,
             tokens: Bucket,
             output_resource_address: ResourceAddress
         ) -> Bucket {
-            // Checking if there does exist a liquidity pool for the given pair of tokens
-            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap"));
-
-            // Sorting the two addresses passed, getting the associated liquidity pool and then performing the swap.
             let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
                 tokens.resource_address(), 
                 output_resource_address
             );
-
-            return self.liquidity_pools[&sorted_addresses].swap(tokens);
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.swap(tokens),
+                None => self.stable_pools[&sorted_addresses].swap(tokens)
+            };
         }
 
-        /// Swaps the exact amount of input tokens for tokens of the desired type.
-        /// 
-        /// This method is used to swap all of the given token (let's say Token A) for their equivalent amount of the
-        /// other token (let's say Token B). This method supports slippage in the form of the `min_amount_out` where
-        /// the caller is given the option to specify the minimum amount of Token B that they're willing to accept for
-        /// the swap to go through. If the output amount does not satisfy the `min_amount_out` specified by the user 
-        /// then this method fails and all of the parties involved get their tokens back.
-        /// 
-        /// This method performs a number of checks before the swap is performed:
-        /// 
-        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens.
+        /// Swaps the input tokens for a specific amount of output tokens through the liquidity pool or the stable pool 
+        /// of the given address pair.
         /// 
         /// # Arguments:
         /// 
         /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
         /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `output_amount` (Decimal) - The specific amount of output tokens to receive.
         /// 
         /// # Returns:
         /// 
         /// * `Bucket` - A bucket of the other tokens.
-        pub fn swap_exact_tokens_for_tokens(
-            &mut self,
+        /// * `Bucket` - A bucket of the remaining input tokens.
+        fn pool_swap_for_exact(
+            &self,
             tokens: Bucket,
             output_resource_address: ResourceAddress,
-            min_amount_out: Decimal
-        ) -> Bucket {
-            // Checking if there does exist a liquidity pool for the given pair of tokens
-            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap Exact"));
-
-            // Sorting the two addresses passed, getting the associated liquidity pool and then performing the swap.
+            output_amount: Decimal
+        ) -> (Bucket, Bucket) {
             let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
                 tokens.resource_address(), 
                 output_resource_address
             );
-            return self.liquidity_pools[&sorted_addresses].swap_exact_tokens_for_tokens(tokens, min_amount_out);
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.swap_tokens_for_exact_tokens(tokens, output_amount),
+                None => self.stable_pools[&sorted_addresses].swap_tokens_for_exact_tokens(tokens, output_amount)
+            };
         }
-        
-        /// Swaps the input tokens for a specific amount of tokens of the desired type.
+
+        /// Calculates the output amount of a swap through the liquidity pool or the stable pool of the given address 
+        /// pair.
         /// 
-        /// This method is used when the user wants to swap a token for a specific amount of another token. This method
-        /// calculates the input amount required to get the desired output and if the amount required is provided in the
-        /// tokens bucket then the swap takes place and the user gets back two buckets: a bucket of the remaining input
-        /// tokens and another bucket of the swapped tokens.
+        /// # Arguments:
         /// 
-        /// This method performs a number of checks before the swap is performed:
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to calculate the output for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The output amount for the given input.
+        fn pool_output_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            input_amount: Decimal
+        ) -> Decimal {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                input_resource_address, 
+                output_resource_address
+            );
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.calculate_output_amount(input_resource_address, input_amount),
+                None => self.stable_pools[&sorted_addresses].calculate_output_amount(input_resource_address, input_amount)
+            };
+        }
+
+        /// Calculates the input amount required for a swap through the liquidity pool or the stable pool of the given
+        /// address pair.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `output_amount` (Decimal) - The amount of output tokens to calculate the input for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The input amount for the given output.
+        fn pool_input_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal
+        ) -> Decimal {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                input_resource_address, 
+                output_resource_address
+            );
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.calculate_input_amount(output_resource_address, output_amount),
+                None => self.stable_pools[&sorted_addresses].calculate_input_amount(output_resource_address, output_amount)
+            };
+        }
+
+        /// Gets the spot price of the input token from the liquidity pool or the stable pool of the given address pair.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the token to get the price of.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token the price is given in.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of output tokens that one input token is worth at the current reserves.
+        fn pool_spot_price(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress
+        ) -> Decimal {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                input_resource_address, 
+                output_resource_address
+            );
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.spot_price(input_resource_address),
+                None => self.stable_pools[&sorted_addresses].spot_price(input_resource_address)
+            };
+        }
+
+        /// Asserts that the given path is a valid swap path through the liquidity pools of the DEX.
+        /// 
+        /// This method performs a number of checks on the path:
+        /// 
+        /// * **Check 1:** Checks that the path contains at least two resource addresses.
+        /// * **Check 2:** Checks that the path starts with the resource address of the input tokens.
+        /// * **Check 3:** Checks that no resource address appears more than once in the path.
+        /// * **Check 4:** Checks that there does exist a liquidity pool for every hop along the path.
+        /// * **Check 5:** Checks that none of the pools along the path have been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the tokens being swapped.
+        /// * `path` (&Vec<ResourceAddress>) - The resource addresses of the tokens to swap through, in order.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_valid_path(
+            &self,
+            input_resource_address: ResourceAddress,
+            path: &Vec<ResourceAddress>,
+            label: String
+        ) {
+            assert!(path.len() >= 2, "[{}]: A path must contain at least two resource addresses.", label);
+            assert_eq!(
+                path[0], input_resource_address,
+                "[{}]: The path must start with the resource address of the input tokens.", 
+                label
+            );
+
+            // A path that visits the same token twice would go through the same liquidity pool more than once, which
+            // is never better than the path without the loop.
+            let unique_addresses: HashSet<ResourceAddress> = path.iter().cloned().collect();
+            assert_eq!(
+                unique_addresses.len(), path.len(),
+                "[{}]: The path may not contain the same resource address more than once.", 
+                label
+            );
+
+            for hop in path.windows(2) {
+                self.assert_pool_exists(hop[0], hop[1], label.clone());
+                self.assert_pool_listed(hop[0], hop[1], label.clone());
+            }
+        }
+
+        /// Checks whether the pool of the given sorted address pair can currently be swapped through.
+        /// 
+        /// A pool can not be swapped through if it has been delisted, if it has been paused or if its trading has been
+        /// halted by the epoch circuit breaker.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `addresses` (&(ResourceAddress, ResourceAddress)) - The sorted resource addresses of the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `bool` - A boolean of whether the pool can be swapped through.
+        fn pool_tradable(
+            &self,
+            addresses: &(ResourceAddress, ResourceAddress)
+        ) -> bool {
+            if self.delisted_pools.contains(addresses) {
+                return false;
+            }
+            return match self.liquidity_pools.get(addresses) {
+                Some(liquidity_pool) => !liquidity_pool.paused() & !liquidity_pool.halted(),
+                None => !self.stable_pools[addresses].paused()
+            };
+        }
+
+        /// Builds a graph of the tokens which have liquidity pools on the DEX that can currently be swapped through.
+        /// 
+        /// Pools which can not be swapped through, as decided by `pool_tradable`, are left out of the graph so that no
+        /// route is found through them. When the DEX is paused the graph is empty.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `HashMap<ResourceAddress, Vec<ResourceAddress>>` - A hashmap that maps the resource address of every 
+        /// token on the DEX to the resource addresses of all of the tokens that it shares a liquidity pool with.
+        fn pool_graph(&self) -> HashMap<ResourceAddress, Vec<ResourceAddress>> {
+            let mut graph: HashMap<ResourceAddress, Vec<ResourceAddress>> = HashMap::new();
+            if self.paused {
+                return graph;
+            }
+
+            let tradable_pools = self.liquidity_pools.keys()
+                .chain(self.stable_pools.keys())
+                .filter(|addresses| self.pool_tradable(addresses));
+            for (address1, address2) in tradable_pools {
+                graph.entry(*address1).or_insert(Vec::new()).push(*address2);
+                graph.entry(*address2).or_insert(Vec::new()).push(*address1);
+            }
+            return graph;
+        }
+
+        /// Finds all of the paths from the last token of the given path to the output token.
+        /// 
+        /// This is a depth first search over the graph of liquidity pools. Tokens that are already in the path are not
+        /// visited again so that all of the paths found are valid swap paths.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `graph` (&HashMap<ResourceAddress, Vec<ResourceAddress>>) - The graph of liquidity pools on the DEX.
+        /// * `path` (&mut Vec<ResourceAddress>) - The path that has been walked so far.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token that the paths end at.
+        /// * `max_hops` (u8) - The maximum number of liquidity pools that a path may go through.
+        /// * `routes` (&mut Vec<Vec<ResourceAddress>>) - The vector that the paths found are pushed into.
+        fn find_routes(
+            &self,
+            graph: &HashMap<ResourceAddress, Vec<ResourceAddress>>,
+            path: &mut Vec<ResourceAddress>,
+            output_resource_address: ResourceAddress,
+            max_hops: u8,
+            routes: &mut Vec<Vec<ResourceAddress>>
+        ) {
+            let current_address: ResourceAddress = *path.last().unwrap();
+            if current_address == output_resource_address {
+                routes.push(path.clone());
+                return;
+            }
+            if path.len() - 1 >= max_hops as usize {
+                return;
+            }
+
+            if let Some(neighbours) = graph.get(&current_address) {
+                for neighbour in neighbours {
+                    if !path.contains(neighbour) {
+                        path.push(*neighbour);
+                        self.find_routes(graph, path, output_resource_address, max_hops, routes);
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        /// Simulates a swap along the given path without moving any funds.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `path` (&Vec<ResourceAddress>) - The resource addresses of the tokens to swap through, in order.
+        /// * `input_amount` (Decimal) - The amount of input tokens to simulate the swap for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of output tokens that the swap would give.
+        fn simulate_path(
+            &self,
+            path: &Vec<ResourceAddress>,
+            input_amount: Decimal
+        ) -> Decimal {
+            let mut amount: Decimal = input_amount;
+            for hop in path.windows(2) {
+                amount = self.pool_output_amount(hop[0], hop[1], amount);
+            }
+            return amount;
+        }
+
+        /// Finds all of the routes between two tokens through the liquidity pools of the DEX.
+        /// 
+        /// This method performs a number of checks before the routes are searched for:
+        /// 
+        /// * **Check 1:** Checks that the input and the output tokens are not the same.
+        /// * **Check 2:** Checks that `max_hops` is at least one.
+        /// * **Check 3:** Checks that at least one route exists between the two tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `max_hops` (u8) - The maximum number of liquidity pools that a route may go through.
+        /// * `label` (String) - The label of the method that called this method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Vec<ResourceAddress>>` - A vector of the paths of all of the routes found.
+        fn routes_between(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            max_hops: u8,
+            label: String
+        ) -> Vec<Vec<ResourceAddress>> {
+            assert_ne!(
+                input_resource_address, output_resource_address,
+                "[{}]: The input and output tokens may not be the same.", 
+                label
+            );
+            assert!(max_hops >= 1, "[{}]: max_hops must be at least 1.", label);
+
+            let graph: HashMap<ResourceAddress, Vec<ResourceAddress>> = self.pool_graph();
+            let mut routes: Vec<Vec<ResourceAddress>> = Vec::new();
+            self.find_routes(&graph, &mut vec![input_resource_address], output_resource_address, max_hops, &mut routes);
+            assert!(!routes.is_empty(), "[{}]: No route exists between the given tokens.", label);
+
+            return routes;
+        }
+
+        /// Simulates each of the given routes and returns the one with the largest output.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `routes` (&Vec<Vec<ResourceAddress>>) - The paths of the routes to pick from.
+        /// * `input_amount` (Decimal) - The amount of input tokens to simulate the routes for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<ResourceAddress>` - The path of the best route.
+        /// * `Decimal` - The expected amount of output tokens when swapping along this path.
+        fn best_of_routes(
+            &self,
+            routes: &Vec<Vec<ResourceAddress>>,
+            input_amount: Decimal
+        ) -> (Vec<ResourceAddress>, Decimal) {
+            let mut best_route: (Vec<ResourceAddress>, Decimal) = (routes[0].clone(), self.simulate_path(&routes[0], input_amount));
+            for route in routes.iter().skip(1) {
+                let output_amount: Decimal = self.simulate_path(route, input_amount);
+                if output_amount > best_route.1 {
+                    best_route = (route.clone(), output_amount);
+                }
+            }
+            return best_route;
+        }
+
+        /// Finds the route through the liquidity pools of the DEX which gives the most output for the given input.
+        /// 
+        /// This method searches the graph of liquidity pools for all of the paths between the input and the output 
+        /// tokens that go through at most `max_hops` liquidity pools. Each of these paths is then simulated using the
+        /// `calculate_output_amount` method of the liquidity pools along it and the path with the largest output is 
+        /// returned. No funds are moved by this method.
+        /// 
+        /// This method performs a number of checks before the route is searched for:
+        /// 
+        /// * **Check 1:** Checks that the input and the output tokens are not the same.
+        /// * **Check 2:** Checks that `max_hops` is at least one.
+        /// * **Check 3:** Checks that at least one route exists between the two tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to find the best route for.
+        /// * `max_hops` (u8) - The maximum number of liquidity pools that the route may go through.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<ResourceAddress>` - The path of the best route, starting with the input token and ending with the 
+        /// output token.
+        /// * `Decimal` - The expected amount of output tokens when swapping along this path.
+        pub fn find_best_route(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            input_amount: Decimal,
+            max_hops: u8
+        ) -> (Vec<ResourceAddress>, Decimal) {
+            let routes: Vec<Vec<ResourceAddress>> = self.routes_between(
+                input_resource_address, 
+                output_resource_address, 
+                max_hops, 
+                String::from("DEX Find Best Route")
+            );
+            let best_route: (Vec<ResourceAddress>, Decimal) = self.best_of_routes(&routes, input_amount);
+            info!("[DEX Find Best Route]: Best route is {:?} giving {}", best_route.0, best_route.1);
+
+            return best_route;
+        }
+
+        /// Quotes a swap of an exact amount of input tokens without performing it.
+        /// 
+        /// This method performs a number of checks before the quote is calculated:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens and that it has not 
+        /// been delisted.
+        /// * **Check 2:** Checks that the input amount is positive.
+        /// * **Check 3:** Checks that the liquidity pool holds reserves of both tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to quote the swap for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Quote` - A quote where `amount` is the amount of output tokens that would be received.
+        pub fn quote_exact_in(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            input_amount: Decimal
+        ) -> Quote {
+            // Checking if there does exist a listed liquidity pool with reserves for the given pair of tokens
+            self.assert_pool_exists(input_resource_address, output_resource_address, String::from("DEX Quote Exact In"));
+            self.assert_pool_listed(input_resource_address, output_resource_address, String::from("DEX Quote Exact In"));
+            assert!(input_amount > Decimal::zero(), "[DEX Quote Exact In]: The input amount must be positive.");
+            let pool_info: PoolInfo = self.pool_info(sort_addresses(input_resource_address, output_resource_address));
+            assert!(
+                (pool_info.reserves.0 > Decimal::zero()) & (pool_info.reserves.1 > Decimal::zero()),
+                "[DEX Quote Exact In]: The pool of the given address pair has no liquidity."
+            );
+
+            // Calculating the quote from the liquidity pool associated with the two addresses
+            let output_amount: Decimal = self.pool_output_amount(input_resource_address, output_resource_address, input_amount);
+            let spot_price: Decimal = self.pool_spot_price(input_resource_address, output_resource_address);
+            let effective_price: Decimal = output_amount / input_amount;
+
+            return Quote {
+                amount: output_amount,
+                effective_price: effective_price,
+                spot_price: spot_price,
+                price_impact: price_impact(spot_price, effective_price)
+            };
+        }
+
+        /// Quotes a swap for an exact amount of output tokens without performing it.
+        /// 
+        /// This method performs a number of checks before the quote is calculated:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens and that it has not 
+        /// been delisted.
+        /// * **Check 2:** Checks that the output amount is positive.
+        /// * **Check 3:** Checks that the liquidity pool holds reserves of both tokens.
+        /// * **Check 4:** Checks that the output amount is less than the reserves of the output token.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `output_amount` (Decimal) - The amount of output tokens to quote the swap for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Quote` - A quote where `amount` is the amount of input tokens that would be required.
+        pub fn quote_exact_out(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal
+        ) -> Quote {
+            // Checking if there does exist a listed liquidity pool with reserves for the given pair of tokens
+            self.assert_pool_exists(input_resource_address, output_resource_address, String::from("DEX Quote Exact Out"));
+            self.assert_pool_listed(input_resource_address, output_resource_address, String::from("DEX Quote Exact Out"));
+            assert!(output_amount > Decimal::zero(), "[DEX Quote Exact Out]: The output amount must be positive.");
+            let pool_info: PoolInfo = self.pool_info(sort_addresses(input_resource_address, output_resource_address));
+            assert!(
+                (pool_info.reserves.0 > Decimal::zero()) & (pool_info.reserves.1 > Decimal::zero()),
+                "[DEX Quote Exact Out]: The pool of the given address pair has no liquidity."
+            );
+            let output_reserve: Decimal = if pool_info.addresses.0 == output_resource_address {
+                pool_info.reserves.0
+            } else {
+                pool_info.reserves.1
+            };
+            assert!(
+                output_amount < output_reserve,
+                "[DEX Quote Exact Out]: The output amount must be less than the reserves of the output token ({}).",
+                output_reserve
+            );
+
+            // Calculating the quote from the liquidity pool associated with the two addresses
+            let input_amount: Decimal = self.pool_input_amount(input_resource_address, output_resource_address, output_amount);
+            let spot_price: Decimal = self.pool_spot_price(input_resource_address, output_resource_address);
+            let effective_price: Decimal = output_amount / input_amount;
+
+            return Quote {
+                amount: input_amount,
+                effective_price: effective_price,
+                spot_price: spot_price,
+                price_impact: price_impact(spot_price, effective_price)
+            };
+        }
+
+        /// Gets the information of the liquidity pool of the given address pair.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `addresses` ((ResourceAddress, ResourceAddress)) - The sorted address pair of the liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `PoolInfo` - The information of the liquidity pool.
+        fn pool_info(
+            &self,
+            addresses: (ResourceAddress, ResourceAddress)
+        ) -> PoolInfo {
+            return match self.liquidity_pools.get(&addresses) {
+                Some(liquidity_pool) => PoolInfo {
+                    addresses: addresses,
+                    tracking_token_address: liquidity_pool.tracking_token_address(),
+                    reserves: (liquidity_pool.reserve(addresses.0), liquidity_pool.reserve(addresses.1)),
+                    fee_to_pool: liquidity_pool.effective_fee(),
+                    name: liquidity_pool.name(),
+                    amplification: None,
+                    fee_tier: None
+                },
+                None => {
+                    let stable_pool: &StablePoolComponent = &self.stable_pools[&addresses];
+                    PoolInfo {
+                        addresses: addresses,
+                        tracking_token_address: stable_pool.tracking_token_address(),
+                        reserves: (stable_pool.reserve(addresses.0), stable_pool.reserve(addresses.1)),
+                        fee_to_pool: stable_pool.fee_to_pool(),
+                        name: stable_pool.name(),
+                        amplification: Some(stable_pool.amplification()),
+                        fee_tier: None
+                    }
+                }
+            };
+        }
+
+        /// Gets the information of the fee tier pool with the given key.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `key` ((ResourceAddress, ResourceAddress, u16)) - The sorted address pair and the fee tier of the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `PoolInfo` - The information of the fee tier pool.
+        fn fee_tier_pool_info(
+            &self,
+            key: (ResourceAddress, ResourceAddress, u16)
+        ) -> PoolInfo {
+            let liquidity_pool: &PoolComponent = &self.fee_tier_pools[&key];
+            return PoolInfo {
+                addresses: (key.0, key.1),
+                tracking_token_address: liquidity_pool.tracking_token_address(),
+                reserves: (liquidity_pool.reserve(key.0), liquidity_pool.reserve(key.1)),
+                fee_to_pool: liquidity_pool.effective_fee(),
+                name: liquidity_pool.name(),
+                amplification: None,
+                fee_tier: Some(key.2)
+            };
+        }
+
+        /// Lists the liquidity pools, the stable pools, and the fee tier pools on the DEX.
+        /// 
+        /// The liquidity pools are ordered by their sorted address pair and then by their fee tier, with the main pool
+        /// of a pair first, so that the order is the same across calls and the pages can be walked through by 
+        /// increasing the `offset`.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `offset` (u64) - The number of liquidity pools to skip.
+        /// * `limit` (u64) - The maximum number of liquidity pools to return.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<PoolInfo>` - The information of the liquidity pools in the requested page.
+        pub fn list_pools(
+            &self,
+            offset: u64,
+            limit: u64
+        ) -> Vec<PoolInfo> {
+            let mut keys: Vec<(ResourceAddress, ResourceAddress, Option<u16>)> = self.liquidity_pools.keys()
+                .chain(self.stable_pools.keys())
+                .map(|addresses| (addresses.0, addresses.1, None))
+                .chain(self.fee_tier_pools.keys().map(|key| (key.0, key.1, Some(key.2))))
+                .collect();
+            keys.sort_by(|a, b| (a.0.to_vec(), a.1.to_vec(), a.2).cmp(&(b.0.to_vec(), b.1.to_vec(), b.2)));
+
+            return keys
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|key| match key.2 {
+                    Some(fee_tier) => self.fee_tier_pool_info((key.0, key.1, fee_tier)),
+                    None => self.pool_info((key.0, key.1))
+                })
+                .collect::<Vec<PoolInfo>>();
+        }
+
+        /// Gets the information of the liquidity pool that issues the given tracking tokens.
+        /// 
+        /// This method performs a number of checks before the information is obtained:
+        /// 
+        /// * **Check 1:** Checks that the tracking tokens belong to one of the liquidity pools or the fee tier pools of
+        /// the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_token_address` (ResourceAddress) - The resource address of the tracking tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `PoolInfo` - The information of the liquidity pool.
+        pub fn get_pool_by_tracking_token(
+            &self,
+            tracking_token_address: ResourceAddress
+        ) -> PoolInfo {
+            if let Some(key) = self.fee_tier_tracking_token_mapping.get(&tracking_token_address) {
+                return self.fee_tier_pool_info(*key);
+            }
+
+            assert!(
+                self.tracking_token_address_pair_mapping.contains_key(&tracking_token_address),
+                "[DEX Get Pool]: The tracking tokens given do not belong to this exchange."
+            );
+
+            return self.pool_info(self.tracking_token_address_pair_mapping[&tracking_token_address]);
+        }
+
+        /// Creates a new liquidity pool in the DEX.
+        /// 
+        /// This method is used to create a new liquidity pool between the two provided tokens on the DEX.
+        /// 
+        /// This method does a number of checks before a Liquidity Pool is created, these checks are:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does not already exist a liquidity pool for the two given tokens.
+        /// 
+        /// The majority of the checking is done in the `new` function of the LiquidityPool where it checks to ensure 
+        /// that the buckets are not empty, tokens are not both the same, as well as other things. The checks done here
+        /// are just DEX checks to ensure that we don't create a liquidity pool for a token pair that already has a 
+        /// liquidity pool.
+        /// 
+        /// # Arguments: 
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token used to initialize the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token used to initialize the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket containing the tracking tokens issued to the creator of the liquidity pool.
+        pub fn new_liquidity_pool(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            fee_to_pool: Decimal
+        ) -> Bucket {
+            self.assert_not_paused(String::from("DEX New Liquidity Pool"));
+            // Checking if a liquidity pool already exists between these two tokens
+            self.assert_pool_doesnt_exists(
+                token1.resource_address(), token2.resource_address(), 
+                String::from("New Liquidity Pool")
+            );
+
+            // Sorting the two buckets according to their resource addresses and creating a liquidity pool from these
+            // two buckets.
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address()); 
+            let (pool, tracking_tokens): (PoolComponent, Bucket) = Pool::instantiate_pool(
+                bucket1, bucket2, fee_to_pool
+
+            );
+            pool.set_protocol_fee(self.protocol_fee);
+            
+            // Adding the liquidity pool to the hashmap of all liquidity pools
+            self.liquidity_pools.insert(
+                addresses,
+                pool
+            );
+
+            // Adding the resource address of the tracking tokens to the hashmap that maps the tracking tokens with 
+            // the address of their token pairs
+            self.tracking_token_address_pair_mapping.insert(
+                tracking_tokens.resource_address(),
+                addresses
+            );
+
+            // The liquidity receipts of the pool are accepted by `remove_liquidity` in place of the tracking tokens, so
+            // they're mapped to the address pair in the same way.
+            self.tracking_token_address_pair_mapping.insert(
+                self.liquidity_pools[&addresses].receipt_address(),
+                addresses
+            );
+            self.flash_loan_receipt_mapping.insert(
+                self.liquidity_pools[&addresses].flash_loan_receipt_address(),
+                (addresses.0, addresses.1, None)
+            );
+            self.flash_swap_receipt_mapping.insert(
+                self.liquidity_pools[&addresses].flash_swap_receipt_address(),
+                (addresses.0, addresses.1, None)
+            );
+
+            // globalize the liquidity pool 
+            // TODO: other than test purposes why do I need this?
+ 
+
+            // Returning the tracking tokens back to the caller of this method (the initial liquidity provider).
+            return tracking_tokens;
+        }
+
+        /// Creates a new stable pool in the DEX.
+        /// 
+        /// This method is used to create a new stable pool between the two provided tokens on the DEX. Stable pools 
+        /// use the StableSwap invariant instead of the constant market maker function and are meant for tokens which 
+        /// trade close to a 1:1 ratio such as two stablecoins or XRD and staked XRD. Once created, the stable pool is
+        /// reachable through the same `swap`, `add_liquidity`, and `remove_liquidity` methods as the liquidity pools.
+        /// 
+        /// This method does a number of checks before a stable pool is created, these checks are:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does not already exist a liquidity pool or a stable pool for the two given
+        /// tokens.
+        /// 
+        /// # Arguments: 
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token used to initialize the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token used to initialize the pool.
+        /// * `fee_to_pool` (Decimal) - The percentage fee paid to the pool on swaps.
+        /// * `amplification` (Decimal) - The amplification coefficient of the StableSwap invariant.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket containing the tracking tokens issued to the creator of the stable pool.
+        pub fn new_stable_pool(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            fee_to_pool: Decimal,
+            amplification: Decimal
+        ) -> Bucket {
+            self.assert_not_paused(String::from("DEX New Stable Pool"));
+            // Checking if a pool already exists between these two tokens
+            self.assert_pool_doesnt_exists(
+                token1.resource_address(), token2.resource_address(), 
+                String::from("New Stable Pool")
+            );
+
+            // Sorting the two buckets according to their resource addresses and creating a stable pool from these two
+            // buckets.
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address()); 
+            let (pool, tracking_tokens): (StablePoolComponent, Bucket) = StablePool::instantiate_pool(
+                bucket1, bucket2, fee_to_pool, amplification
+            );
+            pool.set_protocol_fee(self.protocol_fee);
+
+            // Adding the stable pool to the hashmap of all stable pools and its tracking tokens to the tracking token 
+            // mapping.
+            self.stable_pools.insert(addresses, pool);
+            self.tracking_token_address_pair_mapping.insert(tracking_tokens.resource_address(), addresses);
+
+            return tracking_tokens;
+        }
+
+        /// Adds liquidity to a new or an already existing liquidity pool.
+        /// 
+        /// This method is used to add liquidity to a liquidity pool in the DEX. If a liquidity pool for the two tokens
+        /// passed already exists then liquidity would be directly added to it. The same goes for stable pools. However, 
+        /// if a pool doesn't exist, then a new liquidity pool is created from the two buckets passed to this method.
+        /// 
+        /// When liquidity is added to an existing pool, the method fails if the amounts deposited or the tracking 
+        /// tokens minted fall short of the minimums given, which protects the liquidity provider from the ratio of the
+        /// reserves being moved before the transaction. A new pool takes all of both buckets and sets its own ratio, so
+        /// only the minimum tracking tokens are checked then.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `fee_to_pool` (Decimal) - The percentage fee paid to the pool on swaps if a new pool is created.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the `token1` type.
+        /// * `Bucket` - A bucket of the remaining tokens of the `token2` type.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        pub fn add_liquidity(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            fee_to_pool: Decimal,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Option<Bucket>, Option<Bucket>, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Add Liquidity"));
+            self.assert_not_paused(String::from("DEX Add Liquidity"));
+            // Sorting the two buckets of tokens passed to this method, along with their minimum deposits, and getting 
+            // the addresses of their resources.
+            let token1_address: ResourceAddress = token1.resource_address();
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let (min_amount1, min_amount2): (Decimal, Decimal) = if bucket1.resource_address() == token1_address {
+                (min_token1_deposit, min_token2_deposit)
+            } else {
+                (min_token2_deposit, min_token1_deposit)
+            };
+            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address()); 
+            self.assert_pool_listed(addresses.0, addresses.1, String::from("DEX Add Liquidity"));
+
+            // Stable pools are only ever created explicitly through `new_stable_pool`, so if one exists for this pair then
+            // the liquidity goes directly into it.
+            if let Some(stable_pool) = self.stable_pools.get(&addresses) {
+                info!("[DEX Add Liquidity]: Stable pool for {:?} exists. Adding liquidity directly.", addresses);
+                let returns: (Bucket, Bucket, Bucket) = stable_pool.add_liquidity(
+                    bucket1, bucket2, min_tracking_tokens_out, min_amount1, min_amount2
+                );
+                return (Some(returns.0), Some(returns.1), returns.2);
+            }
+
+            // Attempting to get the liquidity pool component associated with the provided address pair.
+            let optional_liquidity_pool: Option<&PoolComponent> = self.liquidity_pools.get(&addresses);
+            match optional_liquidity_pool {
+                Some (liquidity_pool) => { // If it matches it means that the liquidity pool exists.
+                    info!("[DEX Add Liquidity]: Pool for {:?} already exists. Adding liquidity directly.", addresses);
+                    let returns: (Bucket, Bucket, Bucket) = liquidity_pool.add_liquidity(
+                        bucket1, bucket2, min_tracking_tokens_out, min_amount1, min_amount2
+                    );
+                    (Some(returns.0), Some(returns.1), returns.2)
+                }
+                None => { // If this matches then there does not exist a liquidity pool for this token pair
+                    // In here we are creating a new liquidity pool for this token pair since we failed to find an 
+                    // already existing liquidity pool. The return statement below might seem somewhat redundant in 
+                    // terms of the two empty buckets being returned, but this is done to allow for the add liquidity
+                    // method to be general and allow for the possibility of the liquidity pool not being there.
+                    info!("[DEX Add Liquidity]: Pool for {:?} doesn't exist. Creating a new one.", addresses);
+                    let tracking_tokens: Bucket = self.new_liquidity_pool(bucket1, bucket2, fee_to_pool);
+                    assert!(
+                        tracking_tokens.amount() >= min_tracking_tokens_out,
+                        "[DEX Add Liquidity]: Tracking tokens minted are below the minimum of {}.",
+                        min_tracking_tokens_out
+                    );
+                    (None, None, tracking_tokens)
+                }
+            }
+        }
+
+        /// Adds liquidity to an existing liquidity pool in exchange for a non-fungible liquidity receipt.
+        /// 
+        /// Unlike the fungible tracking tokens, a liquidity receipt records the amounts deposited, the epoch of the 
+        /// deposit, and the pool that the liquidity was added to. This allows the profit and loss and the impermanent
+        /// loss of each position to be computed. The receipt is given to `remove_liquidity` to remove the liquidity.
+        /// 
+        /// This method performs a number of checks before liquidity is added:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the two tokens passed. Receipts are only 
+        /// issued by the constant product liquidity pools.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens the receipt must be worth.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the liquidity receipt issued to the liquidity provider.
+        pub fn add_liquidity_with_receipt(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Add Liquidity With Receipt"));
+            self.assert_not_paused(String::from("DEX Add Liquidity With Receipt"));
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                token1.resource_address(), 
+                token2.resource_address()
+            );
+            assert!(
+                self.liquidity_pools.contains_key(&addresses),
+                "[DEX Add Liquidity With Receipt]: No liquidity pool exists for the given address pair."
+            );
+            self.assert_pool_listed(addresses.0, addresses.1, String::from("DEX Add Liquidity With Receipt"));
+
+            return self.liquidity_pools[&addresses].add_liquidity_with_receipt(
+                token1, token2, min_tracking_tokens_out, min_token1_deposit, min_token2_deposit
+            );
+        }
+
+        /// Removes liquidity from the appropriate liquidity pool in the DEX.
+        /// 
+        /// The main use of this method is to remove liquidity from one of the liquidity pools in the DEX and return 
+        /// back the liquidity provider's share of the liquidity pool. The first thing that this method does is that it
+        /// ensures that the tracking tokens are valid tracking tokens that actually belong to a liquidity pool. This 
+        /// method then finds the liquidity pool that issues the specified tracking tokens and removes the liquidity 
+        /// from it.
+        /// 
+        /// This method performs a number of checks before liquidity removed from the pool:
+        /// 
+        /// * **Check 1:** Checks to ensure that the provided tracking tokens are valid.
+        /// 
+        /// The minimums follow the order of the two tokens as sorted by `sort_addresses`. This is the order that the 
+        /// two buckets are returned in, and the order of `addresses` in the `PoolInfo` of the pool which can be looked
+        /// up through `get_pool_by_tracking_token`.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens, or the liquidity receipts, that the 
+        /// liquidity provider wishes to exchange for their share of the liquidity.
+        /// * `min_amount1` (Decimal) - The minimum amount of the first sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// * `min_amount2` (Decimal) - The minimum amount of the second sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first sorted token.
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second sorted token.
+        pub fn remove_liquidity(
+            &mut self,
+            tracking_tokens: Bucket,
+            min_amount1: Decimal,
+            min_amount2: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Remove Liquidity"));
+            // The tracking tokens of the fee tier pools are mapped separately since they're keyed by the fee tier too
+            if let Some(key) = self.fee_tier_tracking_token_mapping.get(&tracking_tokens.resource_address()) {
+                return self.fee_tier_pools[key].remove_liquidity(tracking_tokens, min_amount1, min_amount2);
+            }
+
+            // Check to make sure that the tracking tokens provided are indeed valid tracking tokens that belong to this
+            // DEX.
+            assert!(
+                self.tracking_token_address_pair_mapping.contains_key(&tracking_tokens.resource_address()),
+                "[DEX Remove Liquidity]: The tracking tokens given do not belong to this exchange."
+            );
+
+            // Getting the address pair associated with the resource address of the tracking tokens and then requesting
+            // the removal of liquidity from the liquidity pool
+            let addresses: (ResourceAddress, ResourceAddress) = self.tracking_token_address_pair_mapping[&tracking_tokens.resource_address()];
+            return match self.liquidity_pools.get(&addresses) {
+                Some(liquidity_pool) => liquidity_pool.remove_liquidity(tracking_tokens, min_amount1, min_amount2),
+                None => self.stable_pools[&addresses].remove_liquidity(tracking_tokens, min_amount1, min_amount2)
+            };
+        }
+
+        /// Swaps the input tokens for tokens of the desired type.
+        /// 
+        /// This method is used to swap tokens for other tokens. This method first checks that there does exist a 
+        /// liquidity pool between the input and the output tokens. If a liquidity pool is found, then the swap goes
+        /// through.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
         /// 
         /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens.
         /// 
@@ -333,25 +1593,2030 @@ mod komo_dex {
         /// 
         /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
         /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap"));
+            self.assert_not_paused(String::from("DEX Swap"));
+            // Checking if there does exist a liquidity pool for the given pair of tokens
+            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap"));
+            self.assert_pool_listed(tokens.resource_address(), output_resource_address, String::from("DEX Swap"));
+
+            // Getting the associated liquidity pool and then performing the swap.
+            return self.pool_swap(tokens, output_resource_address);
+        }
+
+        /// Swaps the exact amount of input tokens for tokens of the desired type.
+        /// 
+        /// This method is used to swap all of the given token (let's say Token A) for their equivalent amount of the
+        /// other token (let's say Token B). This method supports slippage in the form of the `min_amount_out` where
+        /// the caller is given the option to specify the minimum amount of Token B that they're willing to accept for
+        /// the swap to go through. If the output amount does not satisfy the `min_amount_out` specified by the user 
+        /// then this method fails and all of the parties involved get their tokens back.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap_exact_tokens_for_tokens(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Exact"));
+            self.assert_not_paused(String::from("DEX Swap Exact"));
+            // Checking if there does exist a liquidity pool for the given pair of tokens
+            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap Exact"));
+            self.assert_pool_listed(tokens.resource_address(), output_resource_address, String::from("DEX Swap Exact"));
+
+            // Sorting the two addresses passed, getting the associated liquidity pool and then performing the swap.
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                tokens.resource_address(), 
+                output_resource_address
+            );
+            return match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.swap_exact_tokens_for_tokens(tokens, min_amount_out),
+                None => self.stable_pools[&sorted_addresses].swap_exact_tokens_for_tokens(tokens, min_amount_out)
+            };
+        }
+        
+        /// Swaps the input tokens for a specific amount of tokens of the desired type.
+        /// 
+        /// This method is used when the user wants to swap a token for a specific amount of another token. This method
+        /// calculates the input amount required to get the desired output and if the amount required is provided in the
+        /// tokens bucket then the swap takes place and the user gets back two buckets: a bucket of the remaining input
+        /// tokens and another bucket of the swapped tokens.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap_tokens_for_exact_tokens(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Swap For Exact"));
+            self.assert_not_paused(String::from("DEX Swap For Exact"));
+            // Checking if there does exist a liquidity pool for the given pair of tokens
+            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap For Exact"));
+            self.assert_pool_listed(tokens.resource_address(), output_resource_address, String::from("DEX Swap For Exact"));
+
+            // Getting the associated liquidity pool and then performing the swap.
+            return self.pool_swap_for_exact(tokens, output_resource_address, output_amount);
+        }
+
+        /// Swaps the input tokens along a path of liquidity pools.
+        /// 
+        /// This method is used when there does not exist a liquidity pool directly between the input and the output 
+        /// tokens. The tokens are swapped through each of the liquidity pools along the path, where the output of 
+        /// every hop becomes the input of the next hop. As an example, a path of `[A, B, D]` swaps Token A for Token B
+        /// through the A/B pool and then swaps the resulting Token B for Token D through the B/D pool. Slippage is only
+        /// checked against the output of the final hop through `min_amount_out`.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the path is valid and that every hop along it has a liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `path` (Vec<ResourceAddress>) - The resource addresses of the tokens to swap through. The first address
+        /// must be the address of the input tokens and the last address is the address of the desired output tokens.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_via_path(
+            &mut self,
+            tokens: Bucket,
+            path: Vec<ResourceAddress>,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Via Path"));
+            self.assert_not_paused(String::from("DEX Swap Via Path"));
+            // Checking that the path is valid before any of the swaps take place
+            self.assert_valid_path(tokens.resource_address(), &path, String::from("DEX Swap Via Path"));
+
+            // Swapping the tokens through each of the liquidity pools along the path. No slippage checks are done on 
+            // the intermediate hops as only the final output amount matters to the caller.
+            let mut output_tokens: Bucket = tokens;
+            for hop in path.windows(2) {
+                info!(
+                    "[DEX Swap Via Path]: Swapping {} of {:?} for {:?}", 
+                    output_tokens.amount(), hop[0], hop[1]
+                );
+                output_tokens = self.pool_swap(output_tokens, hop[1]);
+            }
+
+            assert!(output_tokens.amount() >= min_amount_out, "[DEX Swap Via Path]: min_amount_out not satisfied.");
+            return output_tokens;
+        }
+
+        /// Swaps the input tokens along the best route found through the liquidity pools of the DEX.
+        /// 
+        /// This method finds the best route through `find_best_route` and then performs the swap along this route 
+        /// through `swap_via_path` within the same method call. Since the route is found and executed atomically, the
+        /// output can not change between the route being found and the swap taking place.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `max_hops` (u8) - The maximum number of liquidity pools that the route may go through.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_best_route(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            max_hops: u8,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Best Route"));
+            let (path, expected_output): (Vec<ResourceAddress>, Decimal) = self.find_best_route(
+                tokens.resource_address(), 
+                output_resource_address, 
+                tokens.amount(), 
+                max_hops
+            );
+            info!("[DEX Swap Best Route]: Swapping along {:?}, expected output: {}", path, expected_output);
+
+            return self.swap_via_path(tokens, path, min_amount_out, None);
+        }
+
+        /// Takes snapshots of the pools along the given path for the simulations of `split_allocation`.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `path` (&Vec<ResourceAddress>) - The resource addresses of the tokens to swap through, in order.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<RouteHop>` - The snapshots of the liquidity pools or stable pools of every hop along the path.
+        fn route_hops(
+            &self,
+            path: &Vec<ResourceAddress>
+        ) -> Vec<RouteHop> {
+            return path.windows(2)
+                .map(|hop| {
+                    let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(hop[0], hop[1]);
+                    match self.liquidity_pools.get(&sorted_addresses) {
+                        Some(liquidity_pool) => RouteHop {
+                            input_reserve: liquidity_pool.reserve(hop[0]),
+                            output_reserve: liquidity_pool.reserve(hop[1]),
+                            fee_modifier: (dec!("100") - liquidity_pool.effective_fee()) / dec!("100"),
+                            stable: None
+                        },
+                        None => {
+                            let stable_pool: &StablePoolComponent = &self.stable_pools[&sorted_addresses];
+                            let input_reserve: Decimal = stable_pool.reserve(hop[0]);
+                            let output_reserve: Decimal = stable_pool.reserve(hop[1]);
+                            let amplification: Decimal = stable_pool.amplification();
+                            RouteHop {
+                                input_reserve: input_reserve,
+                                output_reserve: output_reserve,
+                                fee_modifier: (dec!("100") - stable_pool.fee_to_pool()) / dec!("100"),
+                                stable: Some((amplification, compute_d(input_reserve, output_reserve, amplification)))
+                            }
+                        }
+                    }
+                })
+                .collect::<Vec<RouteHop>>();
+        }
+
+        /// Swaps the input tokens by splitting them across several routes through the liquidity pools of the DEX.
+        /// 
+        /// Swapping a large amount of tokens through a single liquidity pool moves the price along the `x * y = k` 
+        /// curve considerably. This method reduces the price impact by splitting the input tokens across the routes 
+        /// between the two tokens in the proportions which maximize the total output, as found by `split_allocation`.
+        /// At those proportions the last input token swapped along every route used gives the same output, and no 
+        /// unused route would give more.
+        /// 
+        /// The split assumes that the routes are independent of each other. Two routes which go through the same pool 
+        /// are not, since swapping along one moves the price of the other. So the routes are taken from the one which
+        /// gives the most output for the whole input downwards, and any route sharing a pool with one already taken is
+        /// left out. Slippage is only checked once against the total output through `min_amount_out`.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that the input tokens bucket is not empty.
+        /// * **Check 3:** Checks that at least one route exists between the two tokens through pools that can be 
+        /// swapped through.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `max_hops` (u8) - The maximum number of liquidity pools that each route may go through.
+        /// * `min_amount_out` (Decimal) - The minimum total amount of output tokens that the caller is willing to 
+        /// accept before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_split_route(
+            &mut self,
+            mut tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            max_hops: u8,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Split Route"));
+            self.assert_not_paused(String::from("DEX Swap Split Route"));
+            assert!(!tokens.is_empty(), "[DEX Swap Split Route]: Can not swap an empty bucket.");
+
+            // Finding the routes between the two tokens and ordering them from the most output for the whole input to
+            // the least, so that the best routes are kept when routes sharing a pool are left out.
+            let input_amount: Decimal = tokens.amount();
+            let paths: Vec<Vec<ResourceAddress>> = self.routes_between(
+                tokens.resource_address(), 
+                output_resource_address, 
+                max_hops, 
+                String::from("DEX Swap Split Route")
+            );
+            let mut routes: Vec<(Vec<ResourceAddress>, Vec<RouteHop>, Decimal)> = paths.into_iter()
+                .map(|path| {
+                    let hops: Vec<RouteHop> = self.route_hops(&path);
+                    let output_amount: Decimal = route_output_and_marginal(&hops, input_amount).0;
+                    (path, hops, output_amount)
+                })
+                .collect();
+            routes.sort_by(|route1, route2| route2.2.cmp(&route1.2));
+
+            // Keeping only the routes which do not go through any of the pools of a better route
+            let mut used_pools: HashSet<(ResourceAddress, ResourceAddress)> = HashSet::new();
+            let mut independent_routes: Vec<(Vec<ResourceAddress>, Vec<RouteHop>)> = Vec::new();
+            for (path, hops, _) in routes {
+                let pools: Vec<(ResourceAddress, ResourceAddress)> = path.windows(2)
+                    .map(|hop| sort_addresses(hop[0], hop[1]))
+                    .collect();
+                if pools.iter().all(|pool| !used_pools.contains(pool)) {
+                    used_pools.extend(pools);
+                    independent_routes.push((path, hops));
+                }
+            }
+
+            // Splitting the input across the routes and swapping along them. The best route is swapped along last with
+            // whatever remains in the tokens bucket so that no dust is left behind from the search.
+            let allocation: Vec<Decimal> = split_allocation(
+                &independent_routes.iter().map(|route| route.1.clone()).collect(),
+                input_amount
+            );
+            info!("[DEX Swap Split Route]: Splitting {} across the routes as {:?}", input_amount, allocation);
+            let mut output_tokens: Bucket = Bucket::new(output_resource_address);
+            for ((path, _), amount) in independent_routes.iter().zip(allocation.iter()).skip(1) {
+                if *amount > Decimal::zero() {
+                    info!("[DEX Swap Split Route]: Swapping {} along {:?}", amount, path);
+                    output_tokens.put(self.swap_via_path(tokens.take(*amount), path.clone(), Decimal::zero(), None));
+                }
+            }
+            let best_path: Vec<ResourceAddress> = independent_routes[0].0.clone();
+            info!("[DEX Swap Split Route]: Swapping {} along {:?}", tokens.amount(), best_path);
+            output_tokens.put(self.swap_via_path(tokens, best_path, Decimal::zero(), None));
+
+            assert!(output_tokens.amount() >= min_amount_out, "[DEX Swap Split Route]: min_amount_out not satisfied.");
+            return output_tokens;
+        }
+
+        /// Swaps the input tokens for a specific amount of output tokens along a path of liquidity pools.
+        /// 
+        /// This is the exact output equivalent of `swap_via_path`. The path is walked backwards from the desired output
+        /// amount where the `calculate_input_amount` method of each liquidity pool is used to find the amount of tokens
+        /// required at every hop. Once the amount of input tokens required for the whole path is known, only that 
+        /// amount is taken from the tokens bucket and swapped along the path while the rest is returned as change.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the path is valid and that every hop along it has a liquidity pool.
+        /// * **Check 2:** Checks that the input required does not exceed `max_amount_in`.
+        /// * **Check 3:** Checks that the tokens bucket contains the input required.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `path` (Vec<ResourceAddress>) - The resource addresses of the tokens to swap through. The first address
+        /// must be the address of the input tokens and the last address is the address of the desired output tokens.
+        /// * `output_amount` (Decimal) - The specific amount of output tokens that the caller wishes to receive.
+        /// * `max_amount_in` (Decimal) - The maximum amount of input tokens that the caller is willing to give for the
+        /// output before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        /// * `Bucket` - A bucket of the remaining input tokens.
+        pub fn swap_tokens_for_exact_tokens_via_path(
+            &mut self,
+            mut tokens: Bucket,
+            path: Vec<ResourceAddress>,
+            output_amount: Decimal,
+            max_amount_in: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Swap For Exact Via Path"));
+            self.assert_not_paused(String::from("DEX Swap For Exact Via Path"));
+            // Checking that the path is valid before any of the calculations take place
+            self.assert_valid_path(tokens.resource_address(), &path, String::from("DEX Swap For Exact Via Path"));
+
+            // Walking the path backwards to find the amount of tokens required at each one of the hops. `amounts[i]` is
+            // the amount of `path[i]` tokens which need to go into or come out of the swap.
+            let mut amounts: Vec<Decimal> = vec![Decimal::zero(); path.len()];
+            amounts[path.len() - 1] = output_amount;
+            for i in (1..path.len()).rev() {
+                amounts[i - 1] = self.pool_input_amount(path[i - 1], path[i], amounts[i]);
+            }
+            info!("[DEX Swap For Exact Via Path]: Amounts along the path: {:?}", amounts);
+
+            assert!(
+                amounts[0] <= max_amount_in,
+                "[DEX Swap For Exact Via Path]: max_amount_in exceeded. Input required is {}",
+                amounts[0]
+            );
+            assert!(
+                tokens.amount() >= amounts[0],
+                "[DEX Swap For Exact Via Path]: Not enough input for the desired amount of output. Input required is {}",
+                amounts[0]
+            );
+
+            // Performing the swaps along the path. Each liquidity pool calculates the input that it requires from the 
+            // same reserves that were used above, so the exact amount required is passed into every hop and nothing 
+            // remains of the intermediate tokens.
+            let mut hop_tokens: Bucket = tokens.take(amounts[0]);
+            for i in 1..path.len() {
+                let (output_tokens, remaining_tokens): (Bucket, Bucket) = self.pool_swap_for_exact(
+                    hop_tokens, 
+                    path[i], 
+                    amounts[i]
+                );
+                assert!(
+                    remaining_tokens.is_empty(), 
+                    "[DEX Swap For Exact Via Path]: Intermediate hop did not consume all of its input."
+                );
+                hop_tokens = output_tokens;
+            }
+
+            return (hop_tokens, tokens);
+        }
+
+        /// Asserts that a weighted pool for the given set of addresses exists on the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `addresses` (&Vec<ResourceAddress>) - The sorted set of resource addresses of the weighted pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_weighted_pool_exists(
+            &self,
+            addresses: &Vec<ResourceAddress>,
+            label: String
+        ) {
+            assert!(
+                self.weighted_pools.contains_key(addresses), 
+                "[{}]: No weighted pool exists for the given set of addresses.", 
+                label
+            );
+        }
+
+        /// Asserts that the weighted pool of the given set of addresses has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `addresses` (&Vec<ResourceAddress>) - The sorted set of resource addresses of the weighted pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_weighted_pool_listed(
+            &self,
+            addresses: &Vec<ResourceAddress>,
+            label: String
+        ) {
+            assert!(
+                !self.delisted_weighted_pools.contains(addresses), 
+                "[{}]: The weighted pool of the given set of addresses has been delisted.", 
+                label
+            );
+        }
+
+        /// Creates a new weighted pool in the DEX.
+        /// 
+        /// Weighted pools hold two or more tokens where each token makes up a custom share of the value of the pool, 
+        /// such as an 80/20 pool which keeps most of its value in one token. The pool is keyed by the sorted set of
+        /// the addresses of its tokens.
+        /// 
+        /// This method does a number of checks before a weighted pool is created, these checks are:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does not already exist a weighted pool for the given set of tokens.
+        /// 
+        /// The rest of the checks on the tokens and the weights are done by the `instantiate_pool` function of the 
+        /// weighted pool.
+        /// 
+        /// # Arguments: 
+        /// 
+        /// * `tokens` (Vec<Bucket>) - A vector of buckets of the tokens used to initialize the pool.
+        /// * `weights` (Vec<Decimal>) - The weights of the tokens, in the same order as the buckets.
+        /// * `fee_to_pool` (Decimal) - The percentage fee paid to the pool on swaps.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket containing the tracking tokens issued to the creator of the weighted pool.
+        pub fn new_weighted_pool(
+            &mut self,
+            tokens: Vec<Bucket>,
+            weights: Vec<Decimal>,
+            fee_to_pool: Decimal
+        ) -> Bucket {
+            self.assert_not_paused(String::from("DEX New Weighted Pool"));
+            // Checking if a weighted pool already exists for this set of tokens
+            let addresses: Vec<ResourceAddress> = sort_address_set(
+                tokens.iter().map(|token| token.resource_address()).collect()
+            );
+            assert!(
+                !self.weighted_pools.contains_key(&addresses), 
+                "[New Weighted Pool]: A weighted pool with the given set of addresses already exists."
+            );
+
+            let (pool, tracking_tokens): (WeightedPoolComponent, Bucket) = WeightedPool::instantiate_pool(
+                tokens, weights, fee_to_pool
+            );
+            pool.set_protocol_fee(self.protocol_fee);
+
+            // Adding the weighted pool to the hashmap of all weighted pools and its tracking tokens to the weighted 
+            // tracking token mapping.
+            self.weighted_tracking_token_mapping.insert(tracking_tokens.resource_address(), addresses.clone());
+            self.weighted_pools.insert(addresses, pool);
+
+            return tracking_tokens;
+        }
+
+        /// Adds liquidity to an existing weighted pool.
+        /// 
+        /// The method fails if the amounts deposited or the tracking tokens minted fall short of the minimums given, 
+        /// which protects the liquidity provider from the ratio of the reserves being moved before the transaction.
+        /// 
+        /// This method performs a number of checks before liquidity is added:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a weighted pool for the set of tokens passed.
+        /// * **Check 3:** Checks that the weighted pool has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Vec<Bucket>) - A vector of buckets, one for each of the tokens in the weighted pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_deposits` (HashMap<ResourceAddress, Decimal>) - The minimum amount of each token that must be 
+        /// deposited, keyed by the resource address of the token. Tokens without a minimum may be left out.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the remaining tokens.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        pub fn add_weighted_liquidity(
+            &mut self,
+            tokens: Vec<Bucket>,
+            min_tracking_tokens_out: Decimal,
+            min_deposits: HashMap<ResourceAddress, Decimal>,
+            deadline_epoch: Option<u64>
+        ) -> (Vec<Bucket>, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Add Weighted Liquidity"));
+            self.assert_not_paused(String::from("DEX Add Weighted Liquidity"));
+            let addresses: Vec<ResourceAddress> = sort_address_set(
+                tokens.iter().map(|token| token.resource_address()).collect()
+            );
+            self.assert_weighted_pool_exists(&addresses, String::from("DEX Add Weighted Liquidity"));
+            self.assert_weighted_pool_listed(&addresses, String::from("DEX Add Weighted Liquidity"));
+
+            return self.weighted_pools[&addresses].add_liquidity(tokens, min_tracking_tokens_out, min_deposits);
+        }
+
+        /// Removes liquidity from the weighted pool that issued the given tracking tokens.
+        /// 
+        /// The method fails if the amount withdrawn of any of the tokens falls short of its minimum, which protects the
+        /// liquidity provider from the reserves being manipulated just before the transaction.
+        /// 
+        /// This method performs a number of checks before liquidity is removed:
+        /// 
+        /// * **Check 1:** Checks to ensure that the provided tracking tokens belong to one of the weighted pools.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to 
+        /// exchange for their share of the liquidity.
+        /// * `min_amounts` (HashMap<ResourceAddress, Decimal>) - The minimum amount of each token that the liquidity 
+        /// provider is willing to accept before the method fails, keyed by the resource address of the token. Tokens 
+        /// without a minimum may be left out.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the share of the liquidity provider of each token.
+        pub fn remove_weighted_liquidity(
+            &mut self,
+            tracking_tokens: Bucket,
+            min_amounts: HashMap<ResourceAddress, Decimal>,
+            deadline_epoch: Option<u64>
+        ) -> Vec<Bucket> {
+            assert_deadline(deadline_epoch, String::from("DEX Remove Weighted Liquidity"));
+            assert!(
+                self.weighted_tracking_token_mapping.contains_key(&tracking_tokens.resource_address()),
+                "[DEX Remove Weighted Liquidity]: The tracking tokens given do not belong to this exchange."
+            );
+
+            let addresses: Vec<ResourceAddress> = self.weighted_tracking_token_mapping[&tracking_tokens.resource_address()].clone();
+            return self.weighted_pools[&addresses].remove_liquidity(tracking_tokens, min_amounts);
+        }
+
+        /// Swaps the input tokens for the output tokens through a weighted pool.
+        /// 
+        /// Since a pair of tokens may be in any number of weighted pools, the weighted pool to swap through is given 
+        /// by the set of its token addresses.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a weighted pool for the given set of tokens.
+        /// * **Check 3:** Checks that the weighted pool has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `pool_addresses` (Vec<ResourceAddress>) - The resource addresses of the tokens of the weighted pool, in 
+        /// any order.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_weighted(
+            &mut self,
+            tokens: Bucket,
+            pool_addresses: Vec<ResourceAddress>,
+            output_resource_address: ResourceAddress,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Weighted"));
+            self.assert_not_paused(String::from("DEX Swap Weighted"));
+            let addresses: Vec<ResourceAddress> = sort_address_set(pool_addresses);
+            self.assert_weighted_pool_exists(&addresses, String::from("DEX Swap Weighted"));
+            self.assert_weighted_pool_listed(&addresses, String::from("DEX Swap Weighted"));
+
+            return self.weighted_pools[&addresses].swap_exact_tokens_for_tokens(
+                tokens, 
+                output_resource_address, 
+                min_amount_out
+            );
+        }
+
+        /// Asserts that a concentrated pool exists for the given pair of tokens and returns their sorted addresses.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `label` (String) - The label of the method that called this assert method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `(ResourceAddress, ResourceAddress)` - The sorted addresses of the pair.
+        fn assert_concentrated_pool_exists(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            label: String
+        ) -> (ResourceAddress, ResourceAddress) {
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            assert!(
+                self.concentrated_pools.contains_key(&addresses),
+                "[{}]: No concentrated pool exists for the given address pair.",
+                label
+            );
+            return addresses;
+        }
+
+        /// Finds the address pair of the concentrated pool that issued the given position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (&Bucket) - A bucket of the non-fungible token of the position.
+        /// * `label` (String) - The label of the method that called this method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `(ResourceAddress, ResourceAddress)` - The sorted addresses of the pool of the position.
+        fn position_pool_addresses(
+            &self,
+            position: &Bucket,
+            label: String
+        ) -> (ResourceAddress, ResourceAddress) {
+            assert!(
+                self.position_address_pair_mapping.contains_key(&position.resource_address()),
+                "[{}]: The position given does not belong to this exchange.",
+                label
+            );
+            return self.position_address_pair_mapping[&position.resource_address()];
+        }
+
+        /// Creates a new concentrated liquidity pool in the DEX.
+        /// 
+        /// Concentrated pools let liquidity providers choose the price range that their liquidity is used in, so the
+        /// pool starts out empty at the given price and liquidity is added afterwards through
+        /// `add_concentrated_liquidity`.
+        /// 
+        /// This method does a number of checks before a concentrated pool is created, these checks are:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does not already exist a concentrated pool for the two given tokens.
+        /// 
+        /// The rest of the checks are done by the `instantiate_pool` function of the concentrated pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1_address` (ResourceAddress) - The resource address of the first token.
+        /// * `token2_address` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_to_pool` (Decimal) - The percentage fee paid to the active positions on swaps.
+        /// * `initial_price` (Decimal) - The initial price of the first token in terms of the second token.
+        /// * `tick_spacing` (i32) - The spacing between the ticks that positions may be bounded by.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `ResourceAddress` - The resource address of the position non-fungible tokens of the new pool.
+        pub fn new_concentrated_pool(
+            &mut self,
+            token1_address: ResourceAddress,
+            token2_address: ResourceAddress,
+            fee_to_pool: Decimal,
+            initial_price: Decimal,
+            tick_spacing: i32
+        ) -> ResourceAddress {
+            self.assert_not_paused(String::from("DEX New Concentrated Pool"));
+            // Checking if a concentrated pool already exists between these two tokens
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(token1_address, token2_address);
+            assert!(
+                !self.concentrated_pools.contains_key(&addresses),
+                "[New Concentrated Pool]: A concentrated pool with the given address pair already exists."
+            );
+
+            let pool: ConcentratedPoolComponent = ConcentratedPool::instantiate_pool(
+                token1_address, token2_address, fee_to_pool, initial_price, tick_spacing
+            );
+            pool.set_protocol_fee(self.protocol_fee);
+            let position_address: ResourceAddress = pool.position_address();
+
+            // Adding the concentrated pool to the hashmap of all concentrated pools and its positions to the position
+            // mapping.
+            self.position_address_pair_mapping.insert(position_address, addresses);
+            self.concentrated_pools.insert(addresses, pool);
+
+            return position_address;
+        }
+
+        /// Creates a new position in the concentrated pool of the two given tokens.
+        /// 
+        /// This method performs a number of checks before liquidity is added:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a concentrated pool for the two tokens passed.
+        /// * **Check 3:** Checks that the pair has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `lower_tick` (i32) - The lower tick of the price range of the position.
+        /// * `upper_tick` (i32) - The upper tick of the price range of the position.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the non-fungible token representing the position.
+        pub fn add_concentrated_liquidity(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            lower_tick: i32,
+            upper_tick: i32,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Add Concentrated Liquidity"));
+            self.assert_not_paused(String::from("DEX Add Concentrated Liquidity"));
+            let addresses: (ResourceAddress, ResourceAddress) = self.assert_concentrated_pool_exists(
+                token1.resource_address(),
+                token2.resource_address(),
+                String::from("DEX Add Concentrated Liquidity")
+            );
+            self.assert_pool_listed(addresses.0, addresses.1, String::from("DEX Add Concentrated Liquidity"));
+
+            return self.concentrated_pools[&addresses].add_liquidity(token1, token2, lower_tick, upper_tick);
+        }
+
+        /// Removes all of the liquidity of a position along with its uncollected fees and burns the position.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (Bucket) - A bucket of the non-fungible token of the position.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first sorted token.
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second sorted token.
+        pub fn remove_concentrated_liquidity(
+            &mut self,
+            position: Bucket,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Remove Concentrated Liquidity"));
+            let addresses: (ResourceAddress, ResourceAddress) = self.position_pool_addresses(
+                &position,
+                String::from("DEX Remove Concentrated Liquidity")
+            );
+
+            return self.concentrated_pools[&addresses].remove_liquidity(position);
+        }
+
+        /// Collects the fees earned by a position while keeping its liquidity in the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `position` (Bucket) - A bucket of the non-fungible token of the position.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the non-fungible token of the position, which is given back.
+        /// * `Bucket` - A bucket of the fees of the first sorted token.
+        /// * `Bucket` - A bucket of the fees of the second sorted token.
+        pub fn collect_concentrated_fees(
+            &mut self,
+            position: Bucket
+        ) -> (Bucket, Bucket, Bucket) {
+            let addresses: (ResourceAddress, ResourceAddress) = self.position_pool_addresses(
+                &position,
+                String::from("DEX Collect Concentrated Fees")
+            );
+
+            return self.concentrated_pools[&addresses].collect_fees(position);
+        }
+
+        /// Swaps the input tokens for the output tokens through the concentrated pool of the pair.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a concentrated pool for the two tokens.
+        /// * **Check 3:** Checks that the pair has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_concentrated(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Concentrated"));
+            self.assert_not_paused(String::from("DEX Swap Concentrated"));
+            let addresses: (ResourceAddress, ResourceAddress) = self.assert_concentrated_pool_exists(
+                tokens.resource_address(),
+                output_resource_address,
+                String::from("DEX Swap Concentrated")
+            );
+            self.assert_pool_listed(addresses.0, addresses.1, String::from("DEX Swap Concentrated"));
+
+            return self.concentrated_pools[&addresses].swap_exact_tokens_for_tokens(tokens, min_amount_out);
+        }
+
+        /// Sets the percentage of the swap fee of all of the pools which is diverted to the protocol.
+        /// 
+        /// This method is protected by the admin badge of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `protocol_fee` (Decimal) - The percentage of the swap fee to divert to the protocol, between 0 and 100.
+        pub fn set_protocol_fee(
+            &mut self,
+            protocol_fee: Decimal
+        ) {
+            assert!(
+                (protocol_fee >= Decimal::zero()) & (protocol_fee <= dec!("100")), 
+                "[DEX Set Protocol Fee]: Protocol fee must be between 0 and 100"
+            );
+
+            self.protocol_fee = protocol_fee;
+            for liquidity_pool in self.liquidity_pools.values().chain(self.fee_tier_pools.values()) {
+                liquidity_pool.set_protocol_fee(protocol_fee);
+            }
+            for stable_pool in self.stable_pools.values() {
+                stable_pool.set_protocol_fee(protocol_fee);
+            }
+            for weighted_pool in self.weighted_pools.values() {
+                weighted_pool.set_protocol_fee(protocol_fee);
+            }
+            for concentrated_pool in self.concentrated_pools.values() {
+                concentrated_pool.set_protocol_fee(protocol_fee);
+            }
+        }
+
+        /// Sweeps the protocol fees collected by all of the pools to the treasury.
+        /// 
+        /// This method is protected by the admin badge of the DEX. The protocol fees of the same token collected by
+        /// different pools are put into the same bucket.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the protocol fees, one for each of the tokens.
+        pub fn collect_protocol_fees(&mut self) -> Vec<Bucket> {
+            let mut collected: Vec<Bucket> = Vec::new();
+            for liquidity_pool in self.liquidity_pools.values().chain(self.fee_tier_pools.values()) {
+                collected.extend(liquidity_pool.collect_protocol_fees());
+            }
+            for stable_pool in self.stable_pools.values() {
+                collected.extend(stable_pool.collect_protocol_fees());
+            }
+            for weighted_pool in self.weighted_pools.values() {
+                collected.extend(weighted_pool.collect_protocol_fees());
+            }
+            for concentrated_pool in self.concentrated_pools.values() {
+                collected.extend(concentrated_pool.collect_protocol_fees());
+            }
+
+            let mut fees: HashMap<ResourceAddress, Bucket> = HashMap::new();
+            for bucket in collected {
+                match fees.get_mut(&bucket.resource_address()) {
+                    Some(fee_bucket) => fee_bucket.put(bucket),
+                    None => { fees.insert(bucket.resource_address(), bucket); }
+                }
+            }
+            info!("[DEX Collect Protocol Fees]: Collected protocol fees of {} tokens", fees.len());
+
+            // Depositing the fees into the protocol fee recipient if one is set, otherwise returning them to the caller
+            let fees: Vec<Bucket> = fees.into_values().collect::<Vec<Bucket>>();
+            return match self.protocol_fee_recipient {
+                Some(recipient) => {
+                    borrow_component!(recipient).call::<()>("deposit_batch", scrypto_args!(fees));
+                    Vec::new()
+                }
+                None => fees
+            };
+        }
+
+        /// Sets the account that the protocol fees are deposited into when they're collected.
+        /// 
+        /// This method is protected by the admin badge of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `recipient` (Option<ComponentAddress>) - The address of the account to deposit the protocol fees into, or
+        /// `None` for the protocol fees to be returned to the caller of `collect_protocol_fees`.
+        pub fn set_protocol_fee_recipient(
+            &mut self,
+            recipient: Option<ComponentAddress>
+        ) {
+            info!("[DEX Set Protocol Fee Recipient]: Protocol fee recipient set to {:?}", recipient);
+            self.protocol_fee_recipient = recipient;
+        }
+
+        /// Sets the percentage fee paid to the liquidity pool or the stable pool of the given address pair on swaps.
+        /// 
+        /// This method is protected by the admin badge of the DEX. The fee of a fee tier pool is fixed at its tier, 
+        /// since the pool is keyed and routed to by that tier, so fee tier pools can not be reached by this method.
+        /// 
+        /// This method performs a number of checks before the fee is set:
+        /// 
+        /// * **Check 1:** Checks that there does exist a pool for the given pair of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_to_pool` (Decimal) - The new percentage fee paid to the pool on swaps.
+        pub fn set_pool_fee(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_to_pool: Decimal
+        ) {
+            self.assert_pool_exists(address1, address2, String::from("DEX Set Pool Fee"));
+
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            match self.liquidity_pools.get(&sorted_addresses) {
+                Some(liquidity_pool) => liquidity_pool.set_fee_to_pool(fee_to_pool),
+                None => self.stable_pools[&sorted_addresses].set_fee_to_pool(fee_to_pool)
+            };
+        }
+
+        /// Delists the pools of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX. The delisting applies to every pool of the pair: 
+        /// its liquidity pool or stable pool, its fee tier pools, and its concentrated pool. A delisted pool is left 
+        /// out of routing and can no longer be swapped through or have liquidity added to it. Liquidity providers may
+        /// still remove their liquidity from it so that they're never locked in. Weighted pools are delisted through
+        /// `delist_weighted_pool`.
+        /// 
+        /// This method performs a number of checks before the pools are delisted:
+        /// 
+        /// * **Check 1:** Checks that there does exist a pool for the given pair of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        pub fn delist_pool(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress
+        ) {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            assert!(
+                self.pool_exists(address1, address2)
+                | self.concentrated_pools.contains_key(&sorted_addresses)
+                | !self.fee_tiers(address1, address2).is_empty(),
+                "[DEX Delist Pool]: No pool exists for the given address pair."
+            );
+
+            info!("[DEX Delist Pool]: Delisting the pools of {:?}", sorted_addresses);
+            self.delisted_pools.insert(sorted_addresses);
+        }
+
+        /// Delists the weighted pool of the given set of addresses.
+        /// 
+        /// This method is protected by the admin badge of the DEX. A delisted weighted pool can no longer be swapped 
+        /// through or have liquidity added to it, but liquidity providers may still remove their liquidity from it.
+        /// 
+        /// This method performs a number of checks before the pool is delisted:
+        /// 
+        /// * **Check 1:** Checks that there does exist a weighted pool for the given set of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `pool_addresses` (Vec<ResourceAddress>) - The resource addresses of the tokens of the weighted pool, in 
+        /// any order.
+        pub fn delist_weighted_pool(
+            &mut self,
+            pool_addresses: Vec<ResourceAddress>
+        ) {
+            let addresses: Vec<ResourceAddress> = sort_address_set(pool_addresses);
+            self.assert_weighted_pool_exists(&addresses, String::from("DEX Delist Weighted Pool"));
+
+            info!("[DEX Delist Weighted Pool]: Delisting the weighted pool of {:?}", addresses);
+            self.delisted_weighted_pools.insert(addresses);
+        }
+
+        /// Pauses or unpauses the liquidity pool, the stable pool, or the fee tier pool of the given address pair.
+        /// 
+        /// This method performs a number of checks before the pool is paused or unpaused:
+        /// 
+        /// * **Check 1:** Checks that there does exist a pool for the given pair of tokens at the given fee tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `paused` (bool) - Whether the pool should be paused.
+        /// * `label` (String) - The label of the method that called this method.
+        fn set_pool_paused(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>,
+            paused: bool,
+            label: String
+        ) {
+            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            match (fee_tier, self.stable_pools.get(&sorted_addresses)) {
+                (None, Some(stable_pool)) => stable_pool.set_paused(paused),
+                _ => self.liquidity_pool_at_tier(address1, address2, fee_tier, label).set_paused(paused)
+            };
+        }
+
+        /// Pauses the liquidity pool or the stable pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX. A paused pool rejects all swaps and the adding of 
+        /// liquidity, but liquidity providers are still able to remove their liquidity from it. Weighted and 
+        /// concentrated pools are paused through `pause_weighted_pool` and `pause_concentrated_pool`.
+        /// 
+        /// This method performs a number of checks before the pool is paused:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool or a stable pool for the given pair of tokens 
+        /// at the given fee tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        pub fn pause_pool(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>
+        ) {
+            self.set_pool_paused(address1, address2, fee_tier, true, String::from("DEX Pause Pool"));
+        }
+
+        /// Unpauses the liquidity pool or the stable pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        pub fn unpause_pool(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>
+        ) {
+            self.set_pool_paused(address1, address2, fee_tier, false, String::from("DEX Unpause Pool"));
+        }
+
+        /// Pauses the weighted pool of the given set of addresses.
+        /// 
+        /// This method is protected by the admin badge of the DEX. A paused weighted pool rejects all swaps and the 
+        /// adding of liquidity, but liquidity providers are still able to remove their liquidity from it.
+        /// 
+        /// This method performs a number of checks before the pool is paused:
+        /// 
+        /// * **Check 1:** Checks that there does exist a weighted pool for the given set of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `pool_addresses` (Vec<ResourceAddress>) - The resource addresses of the tokens of the weighted pool, in 
+        /// any order.
+        pub fn pause_weighted_pool(
+            &mut self,
+            pool_addresses: Vec<ResourceAddress>
+        ) {
+            let addresses: Vec<ResourceAddress> = sort_address_set(pool_addresses);
+            self.assert_weighted_pool_exists(&addresses, String::from("DEX Pause Weighted Pool"));
+            self.weighted_pools[&addresses].set_paused(true);
+        }
+
+        /// Unpauses the weighted pool of the given set of addresses.
+        /// 
+        /// This method is protected by the admin badge of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `pool_addresses` (Vec<ResourceAddress>) - The resource addresses of the tokens of the weighted pool, in 
+        /// any order.
+        pub fn unpause_weighted_pool(
+            &mut self,
+            pool_addresses: Vec<ResourceAddress>
+        ) {
+            let addresses: Vec<ResourceAddress> = sort_address_set(pool_addresses);
+            self.assert_weighted_pool_exists(&addresses, String::from("DEX Unpause Weighted Pool"));
+            self.weighted_pools[&addresses].set_paused(false);
+        }
+
+        /// Pauses the concentrated pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX. A paused concentrated pool rejects all swaps and the
+        /// creation of positions, but positions can still be removed and their fees collected.
+        /// 
+        /// This method performs a number of checks before the pool is paused:
+        /// 
+        /// * **Check 1:** Checks that there does exist a concentrated pool for the given pair of tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        pub fn pause_concentrated_pool(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress
+        ) {
+            let addresses: (ResourceAddress, ResourceAddress) = self.assert_concentrated_pool_exists(
+                address1, address2, String::from("DEX Pause Concentrated Pool")
+            );
+            self.concentrated_pools[&addresses].set_paused(true);
+        }
+
+        /// Unpauses the concentrated pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        pub fn unpause_concentrated_pool(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress
+        ) {
+            let addresses: (ResourceAddress, ResourceAddress) = self.assert_concentrated_pool_exists(
+                address1, address2, String::from("DEX Unpause Concentrated Pool")
+            );
+            self.concentrated_pools[&addresses].set_paused(false);
+        }
+
+        /// Pauses the whole DEX.
+        /// 
+        /// This method is protected by the admin badge of the DEX. While the DEX is paused, no swaps can be made and no
+        /// liquidity can be added through any of its pools. Liquidity providers are still able to remove their 
+        /// liquidity.
+        pub fn pause(&mut self) {
+            info!("[DEX Pause]: Pausing the DEX");
+            self.paused = true;
+        }
+
+        /// Unpauses the whole DEX.
+        /// 
+        /// This method is protected by the admin badge of the DEX. Liquidity pools which were paused individually stay
+        /// paused until they're unpaused through `unpause_pool`.
+        pub fn unpause(&mut self) {
+            info!("[DEX Unpause]: Unpausing the DEX");
+            self.paused = false;
+        }
+
+        /// Configures the circuit breaker of the liquidity pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX. The circuit breaker rejects any swap which moves the
+        /// spot price of the pool by more than `max_price_movement` percent, and halts the trading of the pool for the
+        /// rest of the epoch once the swaps within the epoch have moved the price by `max_epoch_price_movement` percent 
+        /// in total.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `max_price_movement` (Option<Decimal>) - The maximum percentage that a single swap may move the spot price
+        /// by, or `None` to disable the check.
+        /// * `max_epoch_price_movement` (Option<Decimal>) - The maximum total percentage that the swaps within an epoch
+        /// may move the spot price by, or `None` to disable the check.
+        pub fn set_circuit_breaker(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>,
+            max_price_movement: Option<Decimal>,
+            max_epoch_price_movement: Option<Decimal>
+        ) {
+            self.liquidity_pool_at_tier(address1, address2, fee_tier, String::from("DEX Set Circuit Breaker"))
+                .set_circuit_breaker(max_price_movement, max_epoch_price_movement);
+        }
+
+        /// Configures the dynamic fee of the liquidity pool of the given address pair.
+        /// 
+        /// This method is protected by the admin badge of the DEX. With a dynamic fee, the fee of the pool moves between
+        /// a minimum and a maximum with the volatility of its price over its recent trade history, compensating the 
+        /// liquidity providers during turbulent periods. Passing `None` goes back to the fixed fee of the pool. Like 
+        /// `set_pool_fee`, this method does not reach the fee tier pools since their fee is fixed at their tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `dynamic_fee` (Option<DynamicFeeConfig>) - The configuration of the dynamic fee, or `None` to disable it.
+        pub fn set_dynamic_fee(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            dynamic_fee: Option<DynamicFeeConfig>
+        ) {
+            self.liquidity_pool(address1, address2, String::from("DEX Set Dynamic Fee")).set_dynamic_fee(dynamic_fee);
+        }
+
+        /// Gets the time weighted average price of a token from the liquidity pool of the given address pair.
+        /// 
+        /// The time weighted average price is resistant to manipulation, which makes it suitable as a price oracle for
+        /// other protocols. Only the constant product liquidity pools keep track of their price history.
+        /// 
+        /// This method performs a number of checks before the average price is obtained:
+        /// 
+        /// * **Check 1:** Checks that there does exist a liquidity pool for the given pair of tokens at the given fee 
+        /// tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the token the price is given in.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `window` (u64) - The number of epochs to average the price over, ending at the current epoch.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The average amount of the other token that one of the given token was worth over the window.
+        pub fn twap(
+            &self,
+            resource_address: ResourceAddress,
+            other_resource_address: ResourceAddress,
+            fee_tier: Option<u16>,
+            window: u64
+        ) -> Decimal {
+            return self.liquidity_pool_at_tier(
+                resource_address, other_resource_address, fee_tier, String::from("DEX TWAP")
+            ).twap(resource_address, window);
+        }
+
+        /// Gets the time weighted arithmetic mean price of a token between two epochs from the liquidity pool of the 
+        /// given address pair.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the token the price is given in.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The average amount of the other token that one of the given token was worth over the window.
+        pub fn twap_between(
+            &self,
+            resource_address: ResourceAddress,
+            other_resource_address: ResourceAddress,
+            fee_tier: Option<u16>,
+            start_epoch: u64,
+            end_epoch: u64
+        ) -> Decimal {
+            return self.liquidity_pool_at_tier(
+                resource_address, other_resource_address, fee_tier, String::from("DEX TWAP")
+            ).twap_between(resource_address, start_epoch, end_epoch);
+        }
+
+        /// Gets the time weighted geometric mean price of a token between two epochs from the liquidity pool of the 
+        /// given address pair.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the token the price is given in.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The geometric mean of the amount of the other token that one of the given token was worth over
+        /// the window.
+        pub fn geometric_twap_between(
+            &self,
+            resource_address: ResourceAddress,
+            other_resource_address: ResourceAddress,
+            fee_tier: Option<u16>,
+            start_epoch: u64,
+            end_epoch: u64
+        ) -> Decimal {
+            return self.liquidity_pool_at_tier(
+                resource_address, other_resource_address, fee_tier, String::from("DEX Geometric TWAP")
+            ).geometric_twap_between(resource_address, start_epoch, end_epoch);
+        }
+
+        /// Grows the number of observations that the price oracle of the liquidity pool of the given address pair 
+        /// keeps.
+        /// 
+        /// This method is protected by the admin badge of the DEX since every observation adds to the state of the 
+        /// pool, and with it the cost of every call to the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `cardinality` (u16) - The new maximum number of observations to keep.
+        pub fn grow_observations(
+            &mut self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: Option<u16>,
+            cardinality: u16
+        ) {
+            self.liquidity_pool_at_tier(address1, address2, fee_tier, String::from("DEX Grow Observations"))
+                .grow_observations(cardinality);
+        }
+
+        /// Takes out a flash loan from the liquidity pool of the given address pair.
+        /// 
+        /// The borrowed tokens come with a transient receipt which can not be deposited. The loan must be repaid along
+        /// with its fee through `repay_flash_loan` before the end of the transaction, otherwise the whole transaction 
+        /// fails. The fee of the loan goes to the liquidity providers of the pool.
+        /// 
+        /// This method performs a number of checks before the loan is given:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a liquidity pool for the given pair of tokens at the given fee 
+        /// tier.
+        /// * **Check 3:** Checks that the pool has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the tokens to borrow.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the other token of the pool.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `amount` (Decimal) - The amount of tokens to borrow.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the borrowed tokens.
+        /// * `Bucket` - A bucket of the transient receipt of the flash loan.
+        pub fn flash_loan(
+            &mut self,
+            resource_address: ResourceAddress,
+            other_resource_address: ResourceAddress,
+            fee_tier: Option<u16>,
+            amount: Decimal
+        ) -> (Bucket, Bucket) {
+            self.assert_not_paused(String::from("DEX Flash Loan"));
+            self.assert_pool_listed(resource_address, other_resource_address, String::from("DEX Flash Loan"));
+
+            return self.liquidity_pool_at_tier(
+                resource_address, other_resource_address, fee_tier, String::from("DEX Flash Loan")
+            ).flash_loan(resource_address, amount);
+        }
+
+        /// Repays a flash loan to the liquidity pool that it was taken out from.
+        /// 
+        /// This method performs a number of checks before the loan is repaid:
+        /// 
+        /// * **Check 1:** Checks that the receipt is a flash loan receipt of one of the liquidity pools of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `repayment` (Bucket) - A bucket of the tokens to repay the loan with.
+        /// * `receipt` (Bucket) - A bucket of the transient receipt of the flash loan.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the repayment.
+        pub fn repay_flash_loan(
+            &mut self,
+            repayment: Bucket,
+            receipt: Bucket
+        ) -> Bucket {
+            assert!(
+                self.flash_loan_receipt_mapping.contains_key(&receipt.resource_address()),
+                "[DEX Repay Flash Loan]: The receipt given does not belong to this exchange."
+            );
+
+            let key: (ResourceAddress, ResourceAddress, Option<u16>) = 
+                self.flash_loan_receipt_mapping[&receipt.resource_address()];
+            return self.liquidity_pool_at_tier(key.0, key.1, key.2, String::from("DEX Repay Flash Loan"))
+                .repay_flash_loan(repayment, receipt);
+        }
+
+        /// Takes out a flash swap from the liquidity pool of the given address pair.
+        /// 
+        /// The output tokens come with a transient receipt which can not be deposited. The flash swap must be settled
+        /// with either of the two tokens of the pool through `settle_flash_swap` before the end of the transaction, 
+        /// otherwise the whole transaction fails.
+        /// 
+        /// This method performs a number of checks before the flash swap is made:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a liquidity pool for the given pair of tokens at the given fee 
+        /// tier.
+        /// * **Check 3:** Checks that the pool has not been delisted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the tokens to receive.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the other token of the pool.
+        /// * `fee_tier` (Option<u16>) - The fee tier of the pool in basis points, or `None` for the main pool of the 
+        /// pair.
+        /// * `amount_out` (Decimal) - The amount of tokens to receive.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        /// * `Bucket` - A bucket of the transient receipt of the flash swap.
+        pub fn flash_swap(
+            &mut self,
+            output_resource_address: ResourceAddress,
+            other_resource_address: ResourceAddress,
+            fee_tier: Option<u16>,
+            amount_out: Decimal
+        ) -> (Bucket, Bucket) {
+            self.assert_not_paused(String::from("DEX Flash Swap"));
+            self.assert_pool_listed(output_resource_address, other_resource_address, String::from("DEX Flash Swap"));
+
+            return self.liquidity_pool_at_tier(
+                output_resource_address, other_resource_address, fee_tier, String::from("DEX Flash Swap")
+            ).flash_swap(output_resource_address, amount_out);
+        }
+
+        /// Settles a flash swap with the liquidity pool that it was taken out from.
+        /// 
+        /// This method performs a number of checks before the flash swap is settled:
+        /// 
+        /// * **Check 1:** Checks that the receipt is a flash swap receipt of one of the liquidity pools of the DEX.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `payment` (Bucket) - A bucket of either of the two tokens of the pool to pay for the flash swap with.
+        /// * `receipt` (Bucket) - A bucket of the transient receipt of the flash swap.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the payment.
+        pub fn settle_flash_swap(
+            &mut self,
+            payment: Bucket,
+            receipt: Bucket
+        ) -> Bucket {
+            assert!(
+                self.flash_swap_receipt_mapping.contains_key(&receipt.resource_address()),
+                "[DEX Settle Flash Swap]: The receipt given does not belong to this exchange."
+            );
+
+            let key: (ResourceAddress, ResourceAddress, Option<u16>) = 
+                self.flash_swap_receipt_mapping[&receipt.resource_address()];
+            return self.liquidity_pool_at_tier(key.0, key.1, key.2, String::from("DEX Settle Flash Swap"))
+                .settle_flash_swap(payment, receipt);
+        }
+
+        /// Asserts that a fee tier pool exists for the given address pair and fee tier and returns its key.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// * `fee_tier` (u16) - The fee tier of the pool in basis points.
+        /// * `label` (String) - The label of the method that called this assert method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `(ResourceAddress, ResourceAddress, u16)` - The sorted addresses of the pair and the fee tier.
+        fn assert_fee_tier_pool_exists(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress,
+            fee_tier: u16,
+            label: String
+        ) -> (ResourceAddress, ResourceAddress, u16) {
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            let key: (ResourceAddress, ResourceAddress, u16) = (addresses.0, addresses.1, fee_tier);
+            assert!(
+                self.fee_tier_pools.contains_key(&key),
+                "[{}]: No pool exists for the given address pair at a fee tier of {} basis points.",
+                label, fee_tier
+            );
+            return key;
+        }
+
+        /// Creates a new liquidity pool for the two given tokens at one of the standard fee tiers.
+        /// 
+        /// A pair may have one pool at each of the standard fee tiers on top of the pool created through 
+        /// `new_liquidity_pool`. The pools of the different tiers are independent of each other, each with its own 
+        /// reserves and tracking tokens, which are removed through `remove_liquidity` like any other tracking tokens.
+        /// 
+        /// This method does a number of checks before the pool is created, these checks are:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused and that the pair has not been delisted.
+        /// * **Check 2:** Checks that the fee tier is one of the standard fee tiers.
+        /// * **Check 3:** Checks that there does not already exist a pool for the two tokens at this fee tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token used to initialize the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token used to initialize the pool.
+        /// * `fee_tier` (u16) - The fee tier of the pool in basis points.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket containing the tracking tokens issued to the creator of the pool.
+        pub fn new_fee_tier_pool(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            fee_tier: u16
+        ) -> Bucket {
+            self.assert_not_paused(String::from("DEX New Fee Tier Pool"));
+            self.assert_pool_listed(
+                token1.resource_address(), token2.resource_address(), String::from("DEX New Fee Tier Pool")
+            );
+            assert!(
+                FEE_TIERS.contains(&fee_tier),
+                "[DEX New Fee Tier Pool]: The fee tier must be one of the standard fee tiers {:?}.",
+                FEE_TIERS
+            );
+
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let key: (ResourceAddress, ResourceAddress, u16) = (
+                bucket1.resource_address(), bucket2.resource_address(), fee_tier
+            );
+            assert!(
+                !self.fee_tier_pools.contains_key(&key),
+                "[DEX New Fee Tier Pool]: A pool with the given address pair already exists at this fee tier."
+            );
+
+            // The fee tier is in basis points while the fee of the pool is a percentage
+            let (pool, tracking_tokens): (PoolComponent, Bucket) = Pool::instantiate_pool(
+                bucket1, bucket2, Decimal::from(fee_tier) / dec!("100")
+            );
+            pool.set_protocol_fee(self.protocol_fee);
+
+            // Mapping the tracking tokens and the liquidity receipts of the pool to its key
+            self.fee_tier_tracking_token_mapping.insert(tracking_tokens.resource_address(), key);
+            self.fee_tier_tracking_token_mapping.insert(pool.receipt_address(), key);
+            self.flash_loan_receipt_mapping.insert(pool.flash_loan_receipt_address(), (key.0, key.1, Some(fee_tier)));
+            self.flash_swap_receipt_mapping.insert(pool.flash_swap_receipt_address(), (key.0, key.1, Some(fee_tier)));
+            self.fee_tier_pools.insert(key, pool);
+
+            return tracking_tokens;
+        }
+
+        /// Gets the fee tiers that the given pair has pools at.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address1` (ResourceAddress) - The resource address of the first token.
+        /// * `address2` (ResourceAddress) - The resource address of the second token.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<u16>` - The fee tiers in basis points that the pair has pools at.
+        pub fn fee_tiers(
+            &self,
+            address1: ResourceAddress,
+            address2: ResourceAddress
+        ) -> Vec<u16> {
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(address1, address2);
+            return FEE_TIERS.iter()
+                .filter(|fee_tier| self.fee_tier_pools.contains_key(&(addresses.0, addresses.1, **fee_tier)))
+                .map(|fee_tier| *fee_tier)
+                .collect::<Vec<u16>>();
+        }
+
+        /// Adds liquidity to the pool of the two given tokens at the given fee tier.
+        /// 
+        /// This method performs a number of checks before liquidity is added:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused and that the pair has not been delisted.
+        /// * **Check 2:** Checks that there does exist a pool for the two tokens at the given fee tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `fee_tier` (u16) - The fee tier of the pool in basis points.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        pub fn add_fee_tier_liquidity(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            fee_tier: u16,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Add Fee Tier Liquidity"));
+            self.assert_not_paused(String::from("DEX Add Fee Tier Liquidity"));
+            self.assert_pool_listed(
+                token1.resource_address(), token2.resource_address(), String::from("DEX Add Fee Tier Liquidity")
+            );
+            let key: (ResourceAddress, ResourceAddress, u16) = self.assert_fee_tier_pool_exists(
+                token1.resource_address(), token2.resource_address(), fee_tier, 
+                String::from("DEX Add Fee Tier Liquidity")
+            );
+
+            return self.fee_tier_pools[&key].add_liquidity(
+                token1, token2, min_tracking_tokens_out, min_token1_deposit, min_token2_deposit
+            );
+        }
+
+        /// Swaps the input tokens through the pool of the pair at the given fee tier.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused and that the pair has not been delisted.
+        /// * **Check 2:** Checks that there does exist a pool for the two tokens at the given fee tier.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `fee_tier` (u16) - The fee tier of the pool in basis points.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
         /// 
         /// # Returns:
         /// 
-        /// * `Bucket` - A bucket of the other tokens.
-        pub fn swap_tokens_for_exact_tokens(
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_fee_tier(
             &mut self,
             tokens: Bucket,
             output_resource_address: ResourceAddress,
-            output_amount: Decimal
-        ) -> (Bucket, Bucket) {
-            // Checking if there does exist a liquidity pool for the given pair of tokens
-            self.assert_pool_exists(tokens.resource_address(), output_resource_address, String::from("DEX Swap For Exact"));
+            fee_tier: u16,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Fee Tier"));
+            self.assert_not_paused(String::from("DEX Swap Fee Tier"));
+            self.assert_pool_listed(
+                tokens.resource_address(), output_resource_address, String::from("DEX Swap Fee Tier")
+            );
+            let key: (ResourceAddress, ResourceAddress, u16) = self.assert_fee_tier_pool_exists(
+                tokens.resource_address(), output_resource_address, fee_tier, String::from("DEX Swap Fee Tier")
+            );
 
-            // Sorting the two addresses passed, getting the associated liquidity pool and then performing the swap.
-            let sorted_addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
-                tokens.resource_address(), 
-                output_resource_address
+            return self.fee_tier_pools[&key].swap_exact_tokens_for_tokens(tokens, min_amount_out);
+        }
+
+        /// Swaps the input tokens through whichever pool of the pair gives the most output tokens.
+        /// 
+        /// The candidates are the pools of the pair at all of the fee tiers and the pool of the pair in 
+        /// `liquidity_pools`. A lower fee does not always win, as a pool with a higher fee may have deeper reserves and
+        /// so a lower price impact, so the output of every candidate is calculated at its current reserves.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the DEX is not paused and that the pair has not been delisted.
+        /// * **Check 2:** Checks that there is at least one liquidity pool for the two tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `min_amount_out` (Decimal) - The minimum amount of output tokens that the caller is willing to accept
+        /// before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_best_fee_tier(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Swap Best Fee Tier"));
+            self.assert_not_paused(String::from("DEX Swap Best Fee Tier"));
+            self.assert_pool_listed(
+                tokens.resource_address(), output_resource_address, String::from("DEX Swap Best Fee Tier")
+            );
+
+            let addresses: (ResourceAddress, ResourceAddress) = sort_addresses(
+                tokens.resource_address(), output_resource_address
+            );
+            let mut candidates: Vec<&PoolComponent> = FEE_TIERS.iter()
+                .filter_map(|fee_tier| self.fee_tier_pools.get(&(addresses.0, addresses.1, *fee_tier)))
+                .collect::<Vec<&PoolComponent>>();
+            if let Some(liquidity_pool) = self.liquidity_pools.get(&addresses) {
+                candidates.push(liquidity_pool);
+            }
+            assert!(
+                !candidates.is_empty(),
+                "[DEX Swap Best Fee Tier]: No liquidity pool exists for the given address pair."
+            );
+
+            // Picking the pool which gives the most output tokens for the input
+            let mut best_pool: &PoolComponent = candidates[0];
+            let mut best_amount: Decimal = best_pool.calculate_output_amount(
+                tokens.resource_address(), tokens.amount()
+            );
+            for pool in candidates.into_iter().skip(1) {
+                let amount: Decimal = pool.calculate_output_amount(tokens.resource_address(), tokens.amount());
+                if amount > best_amount {
+                    best_pool = pool;
+                    best_amount = amount;
+                }
+            }
+            info!("[DEX Swap Best Fee Tier]: Best pool gives {} of {:?}", best_amount, output_resource_address);
+
+            return best_pool.swap_exact_tokens_for_tokens(tokens, min_amount_out);
+        }
+
+        /// Adds liquidity to the liquidity pool of a pair from a single one of its tokens.
+        /// 
+        /// Liquidity providers often only hold one of the two tokens of a pool. This method swaps the optimal portion 
+        /// of the given tokens for the other token through the same liquidity pool and then adds the result as 
+        /// liquidity, so that as little as possible of either token is left over. Whatever is left over is returned 
+        /// along with the tracking tokens.
+        /// 
+        /// This method performs a number of checks before liquidity is added:
+        /// 
+        /// * **Check 1:** Checks that the deadline has not passed and that the DEX is not paused.
+        /// * **Check 2:** Checks that there does exist a liquidity pool for the given pair of tokens.
+        /// * **Check 3:** Checks that the pool has not been delisted.
+        /// * **Check 4:** Checks that the tracking tokens minted are not below the minimum.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket of the tokens to add as liquidity.
+        /// * `other_resource_address` (ResourceAddress) - The resource address of the other token of the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        /// 
+        /// # Note:
+        /// 
+        /// Say that `a` is the amount of tokens given, `A` is the reserve of that token in the pool, and `r` is one 
+        /// minus the fee of the pool. Swapping `s` of the tokens gives `(B * r * s) / (A + r * s)` of the other token, 
+        /// and for nothing to be left over the ratio of `(a - s)` to that output has to be equal to the ratio of the 
+        /// reserves after the swap. Solving this for `s` gives the quadratic `r * s^2 + (1 + r) * A * s - A * a = 0`,
+        /// whose positive root is:
+        /// 
+        /// `s = (sqrt(((1 + r) * A)^2 + 4 * r * A * a) - (1 + r) * A) / (2 * r)`
+        pub fn zap_in(
+            &mut self,
+            mut tokens: Bucket,
+            other_resource_address: ResourceAddress,
+            min_tracking_tokens_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> (Bucket, Bucket, Bucket) {
+            assert_deadline(deadline_epoch, String::from("DEX Zap In"));
+            self.assert_not_paused(String::from("DEX Zap In"));
+            self.assert_pool_listed(tokens.resource_address(), other_resource_address, String::from("DEX Zap In"));
+            let liquidity_pool: &PoolComponent = self.liquidity_pool(
+                tokens.resource_address(), other_resource_address, String::from("DEX Zap In")
+            );
+
+            // Finding the amount to swap from the positive root of the quadratic
+            let a: Decimal = tokens.amount();
+            let reserve: Decimal = liquidity_pool.reserve(tokens.resource_address());
+            let r: Decimal = (dec!("100") - liquidity_pool.effective_fee()) / dec!("100");
+            let b: Decimal = (Decimal::one() + r) * reserve;
+            let swap_amount: Decimal = (sqrt(b * b + dec!("4") * r * reserve * a) - b) / (dec!("2") * r);
+            info!("[DEX Zap In]: Swapping {} of the {} tokens given before adding liquidity", swap_amount, a);
+
+            // Swapping through the same pool and then adding both tokens as liquidity
+            let other_tokens: Bucket = liquidity_pool.swap(tokens.take(swap_amount));
+            return liquidity_pool.add_liquidity(
+                tokens, other_tokens, min_tracking_tokens_out, Decimal::zero(), Decimal::zero()
+            );
+        }
+
+        /// Removes liquidity from a liquidity pool and receives all of it as a single one of the two tokens.
+        /// 
+        /// This is the complement of `zap_in`. The tracking tokens are exchanged for the share of the liquidity 
+        /// provider of both tokens through `remove_liquidity`, and then the share of the unwanted token is swapped for
+        /// the desired token through the same liquidity pool.
+        /// 
+        /// This method performs a number of checks before liquidity is removed:
+        /// 
+        /// * **Check 1:** Checks that the deadline has not passed and that the DEX is not paused, since this method 
+        /// swaps through the pool.
+        /// * **Check 2:** Checks that the tracking tokens belong to one of the constant product liquidity pools of the
+        /// DEX, including the pools of the fee tiers.
+        /// * **Check 3:** Checks that the pool has not been delisted and that the desired token belongs to it.
+        /// * **Check 4:** Checks that the pool is not emptied out by the removal, as there would be nothing left to swap
+        /// the unwanted token against. The last of the liquidity is removed through `remove_liquidity` instead.
+        /// * **Check 5:** Checks that the amount of the desired token received is not below the minimum.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to 
+        /// exchange for their share of the liquidity.
+        /// * `desired_resource_address` (ResourceAddress) - The resource address of the token to receive.
+        /// * `min_amount_out` (Decimal) - The minimum amount of the desired token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no
+        /// deadline.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the desired tokens.
+        pub fn zap_out(
+            &mut self,
+            tracking_tokens: Bucket,
+            desired_resource_address: ResourceAddress,
+            min_amount_out: Decimal,
+            deadline_epoch: Option<u64>
+        ) -> Bucket {
+            assert_deadline(deadline_epoch, String::from("DEX Zap Out"));
+            self.assert_not_paused(String::from("DEX Zap Out"));
+
+            // Finding the liquidity pool of the tracking tokens, which may be the pool of a fee tier
+            let (addresses, liquidity_pool): ((ResourceAddress, ResourceAddress), &PoolComponent) = 
+                match self.fee_tier_tracking_token_mapping.get(&tracking_tokens.resource_address()) {
+                    Some(key) => ((key.0, key.1), &self.fee_tier_pools[key]),
+                    None => {
+                        assert!(
+                            self.tracking_token_address_pair_mapping.contains_key(&tracking_tokens.resource_address()),
+                            "[DEX Zap Out]: The tracking tokens given do not belong to this exchange."
+                        );
+                        let addresses: (ResourceAddress, ResourceAddress) = 
+                            self.tracking_token_address_pair_mapping[&tracking_tokens.resource_address()];
+                        assert!(
+                            self.liquidity_pools.contains_key(&addresses),
+                            "[DEX Zap Out]: Zapping out is only supported by the constant product liquidity pools."
+                        );
+                        (addresses, &self.liquidity_pools[&addresses])
+                    }
+                };
+            self.assert_pool_listed(addresses.0, addresses.1, String::from("DEX Zap Out"));
+            liquidity_pool.assert_belongs_to_pool(desired_resource_address, String::from("DEX Zap Out"));
+
+            // Removing the liquidity and swapping the unwanted side for the desired token through the same pool
+            let (bucket1, bucket2): (Bucket, Bucket) = liquidity_pool.remove_liquidity(
+                tracking_tokens, Decimal::zero(), Decimal::zero()
             );
-            return self.liquidity_pools[&sorted_addresses].swap_tokens_for_exact_tokens(tokens, output_amount);
+            let (mut desired_tokens, unwanted_tokens): (Bucket, Bucket) = 
+                if bucket1.resource_address() == desired_resource_address {
+                    (bucket1, bucket2)
+                } else {
+                    (bucket2, bucket1)
+                };
+            assert!(
+                liquidity_pool.reserve(desired_resource_address) > Decimal::zero(),
+                "[DEX Zap Out]: Can not zap out all of the liquidity of the pool. Use remove_liquidity instead."
+            );
+            info!(
+                "[DEX Zap Out]: Removed {} of the desired token, swapping {} of the other token", 
+                desired_tokens.amount(), unwanted_tokens.amount()
+            );
+
+            // The swap has to make up whatever the removal fell short of the minimum by
+            let min_swap_out: Decimal = if min_amount_out > desired_tokens.amount() {
+                min_amount_out - desired_tokens.amount()
+            } else {
+                Decimal::zero()
+            };
+            desired_tokens.put(liquidity_pool.swap_exact_tokens_for_tokens(unwanted_tokens, min_swap_out));
+            return desired_tokens;
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant_product_hop(input_reserve: Decimal, output_reserve: Decimal) -> RouteHop {
+        return RouteHop {
+            input_reserve: input_reserve,
+            output_reserve: output_reserve,
+            fee_modifier: dec!("0.997"),
+            stable: None
+        };
+    }
+
+    /// Swaps the input amount across the routes as `swap_split_route` does, with any input that the allocation leaves
+    /// over swapped along the first route.
+    fn split_output(routes: &Vec<Vec<RouteHop>>, input_amount: Decimal) -> Decimal {
+        let allocation: Vec<Decimal> = split_allocation(routes, input_amount);
+        let allocated: Decimal = allocation.iter().fold(Decimal::zero(), |total, amount| total + *amount);
+        assert!(allocated <= input_amount, "{} allocated out of {}", allocated, input_amount);
+
+        let remainder: Decimal = input_amount - allocated;
+        let mut output_amount: Decimal = route_output_and_marginal(&routes[0], allocation[0] + remainder).0;
+        for (hops, amount) in routes.iter().zip(allocation.iter()).skip(1) {
+            output_amount = output_amount + route_output_and_marginal(hops, *amount).0;
+        }
+        return output_amount;
+    }
+
+    #[test]
+    fn split_allocation_beats_the_best_single_route() {
+        let routes: Vec<Vec<RouteHop>> = vec![
+            vec![constant_product_hop(dec!("1000"), dec!("1000"))],
+            vec![constant_product_hop(dec!("500"), dec!("1000")), constant_product_hop(dec!("2000"), dec!("1000"))]
+        ];
+        let input_amount: Decimal = dec!("200");
+
+        let best_single_output: Decimal = routes.iter()
+            .map(|hops| route_output_and_marginal(hops, input_amount).0)
+            .max()
+            .unwrap();
+        assert!(split_output(&routes, input_amount) > best_single_output);
+    }
+
+    #[test]
+    fn split_allocation_equalizes_the_marginal_outputs() {
+        let routes: Vec<Vec<RouteHop>> = vec![
+            vec![constant_product_hop(dec!("1000"), dec!("1000"))],
+            vec![constant_product_hop(dec!("3000"), dec!("3000"))]
+        ];
+        let allocation: Vec<Decimal> = split_allocation(&routes, dec!("400"));
+
+        // Two pools at the same price and fee take the input in proportion to their reserves
+        assert!((allocation[0] - dec!("100")).abs() < dec!("0.000001"), "First route took {}", allocation[0]);
+        assert!((allocation[1] - dec!("300")).abs() < dec!("0.000001"), "Second route took {}", allocation[1]);
+    }
+
+    #[test]
+    fn split_allocation_leaves_out_routes_which_are_never_better() {
+        let routes: Vec<Vec<RouteHop>> = vec![
+            vec![constant_product_hop(dec!("1000"), dec!("1000"))],
+            vec![constant_product_hop(dec!("1000"), dec!("500"))]
+        ];
+        let allocation: Vec<Decimal> = split_allocation(&routes, dec!("10"));
+        assert_eq!(allocation[1], Decimal::zero());
+    }
+
+    #[test]
+    fn route_amount_at_marginal_inverts_the_marginal_output() {
+        let hops: Vec<RouteHop> = vec![
+            constant_product_hop(dec!("500"), dec!("1000")), 
+            constant_product_hop(dec!("2000"), dec!("1000"))
+        ];
+        let marginal: Decimal = dec!("0.8");
+        let amount: Decimal = route_amount_at_marginal(&hops, marginal, dec!("1000000"));
+        let marginal_at_amount: Decimal = route_output_and_marginal(&hops, amount).1;
+        assert!(
+            (marginal_at_amount - marginal).abs() < dec!("0.000000001"), 
+            "Marginal output at {} is {}", amount, marginal_at_amount
+        );
+    }
+}
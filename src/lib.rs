@@ -1,3 +1,6 @@
 pub mod utils;
 pub mod liquidity_pool;
+pub mod stable_pool;
+pub mod weighted_pool;
+pub mod concentrated_pool;
 pub mod komo_dex;
@@ -1,6 +1,130 @@
 use scrypto::prelude::*;
 use crate::utils::*;
 
+/// The data of the non-fungible liquidity receipts issued by a liquidity pool. A receipt records the details of the
+/// deposit that it was issued for so that the profit and loss and the impermanent loss of every position may be 
+/// computed from the amounts that the position is worth when it is removed.
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct LiquidityReceipt {
+    /// The id of the liquidity pool that the liquidity was added to, made up of the addresses of its two tokens.
+    pub lp_id: String,
+
+    /// The resource addresses of the two tokens of the liquidity pool in sorted order.
+    pub addresses: (ResourceAddress, ResourceAddress),
+
+    /// The amounts of the two tokens which were deposited into the liquidity pool, in the same order as `addresses`.
+    pub deposit_amounts: (Decimal, Decimal),
+
+    /// The epoch in which the liquidity was added.
+    pub deposit_epoch: u64,
+
+    /// The amount of tracking tokens that were issued for the deposit and which are held by the pool on behalf of the
+    /// receipt.
+    pub tracking_amount: Decimal
+}
+
+/// The data of the transient receipts of the flash loans of a liquidity pool. A flash loan receipt can not be 
+/// deposited anywhere, so the only way to get rid of it before the end of the transaction is to repay the loan.
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct FlashLoanReceipt {
+    /// The resource address of the tokens which were borrowed.
+    pub resource_address: ResourceAddress,
+
+    /// The amount of tokens which were borrowed.
+    pub amount: Decimal,
+
+    /// The fee which must be paid on top of the amount borrowed when the loan is repaid.
+    pub fee: Decimal
+}
+
+/// The data of the transient receipts of the flash swaps of a liquidity pool. As with flash loans, the receipt can not
+/// be deposited anywhere, so the flash swap must be settled before the end of the transaction.
+#[derive(NonFungibleData, ScryptoSbor)]
+pub struct FlashSwapReceipt {
+    /// The resource address of the tokens which were given out.
+    pub output_resource_address: ResourceAddress,
+
+    /// The amount of tokens which were given out.
+    pub amount_out: Decimal,
+
+    /// The reserves of the other token and of the output token before the flash swap.
+    pub reserves: (Decimal, Decimal),
+
+    /// The vault price of the pool before the flash swap, used to check the circuit breaker when it's settled.
+    pub price_before: Decimal
+}
+
+/// The configuration of the dynamic fee of a liquidity pool. When a pool has a dynamic fee, the fee paid on swaps 
+/// moves between `min_fee` and `max_fee` with the volatility of the price of the pool over the last `window` epochs, so
+/// that the liquidity providers earn more during turbulent periods when they're exposed to more impermanent loss.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct DynamicFeeConfig {
+    /// The percentage fee paid when the price of the pool has not moved.
+    pub min_fee: Decimal,
+
+    /// The percentage fee paid when the volatility reaches `volatility_cap`.
+    pub max_fee: Decimal,
+
+    /// The volatility, as the percentage range of the trade prices over the window, at which the fee reaches `max_fee`.
+    pub volatility_cap: Decimal,
+
+    /// The number of past epochs of trade history that the volatility is measured over.
+    pub window: u64
+}
+
+/// The maximum number of trade prices that a liquidity pool keeps for measuring its volatility. Once full, the oldest
+/// trade price is dropped for every new one.
+const MAX_TRADE_HISTORY: usize = 64;
+
+/// The default number of observations that the oracle of a liquidity pool keeps. This may be grown per pool through
+/// `grow_observations`.
+const DEFAULT_OBSERVATION_CARDINALITY: u16 = 32;
+
+/// An observation of the oracle accumulators of a liquidity pool, recorded on the first update of the pool in an epoch.
+/// Each accumulator is the sum of a value of the pool weighted by the number of epochs that the pool stayed at it, so 
+/// the average of the value between two epochs is the difference of the accumulators divided by the epochs between.
+#[derive(ScryptoSbor, Debug, Clone)]
+pub struct Observation {
+    /// The epoch that the observation was recorded in.
+    pub epoch: u64,
+
+    /// The cumulative prices of the first and second sorted tokens.
+    pub price_cumulative: (Decimal, Decimal),
+
+    /// The cumulative natural logarithm of the price of the first sorted token. The cumulative logarithm of the price
+    /// of the second sorted token is its negative.
+    pub log_price_cumulative: Decimal,
+
+    /// The cumulative liquidity of the pool, measured as `sqrt(x * y)`.
+    pub liquidity_cumulative: Decimal
+}
+
+/// Interpolates the accumulators between two observations.
+/// 
+/// # Arguments:
+/// 
+/// * `before` (&Observation) - The observation before the epoch.
+/// * `after` (&Observation) - The observation after the epoch.
+/// * `epoch` (u64) - The epoch to find the value of the accumulators at.
+/// 
+/// # Returns:
+/// 
+/// * `Observation` - The value of the accumulators at the given epoch.
+fn interpolate(before: &Observation, after: &Observation, epoch: u64) -> Observation {
+    let fraction: Decimal = Decimal::from(epoch - before.epoch) / Decimal::from(after.epoch - before.epoch);
+    return Observation {
+        epoch: epoch,
+        price_cumulative: (
+            before.price_cumulative.0 + (after.price_cumulative.0 - before.price_cumulative.0) * fraction,
+            before.price_cumulative.1 + (after.price_cumulative.1 - before.price_cumulative.1) * fraction
+        ),
+        log_price_cumulative: before.log_price_cumulative 
+            + (after.log_price_cumulative - before.log_price_cumulative) * fraction,
+        liquidity_cumulative: before.liquidity_cumulative 
+            + (after.liquidity_cumulative - before.liquidity_cumulative) * fraction
+    };
+}
+
 #[blueprint]
 mod pool {
 
@@ -22,7 +146,86 @@ mod pool {
        /// Decimal Amount between 0 to 100 representing the percentage fee 
        /// paid to liquidity pool (to be distributed to the liquidity providers 
        /// based on thier LP tracking token ratio )
-       fee_to_pool: Decimal
+       fee_to_pool: Decimal,
+
+       /// The resource address of the non-fungible liquidity receipts. Receipts are an alternative to holding the
+       /// tracking tokens directly, where the tracking tokens are held by the pool and the receipt records the deposit
+       receipt_address: ResourceAddress,
+
+       /// Vault holding the tracking tokens of all of the outstanding liquidity receipts
+       receipt_tracking_tokens: Vault,
+
+       /// The id of the next liquidity receipt to be minted
+       receipt_counter: u64,
+
+       /// Decimal Amount between 0 to 100 representing the percentage of the swap 
+       /// fee which is diverted to the protocol instead of the liquidity providers
+       protocol_fee: Decimal,
+
+       /// Vaults holding the protocol fees collected from swaps until they're collected
+       protocol_vaults: HashMap<ResourceAddress, Vault>,
+
+       /// When the pool is paused no swaps can be made and no liquidity can be added,
+       /// but liquidity providers are still able to remove their liquidity
+       paused: bool,
+
+       /// The maximum percentage that a single swap may move the spot price of the pool by.
+       /// Swaps which move the price by more than this are rejected. `None` disables the check
+       max_price_movement: Option<Decimal>,
+
+       /// The maximum total percentage that the swaps within a single epoch may move the spot
+       /// price by. Once reached, the pool halts trading until the next epoch. `None` disables the check
+       max_epoch_price_movement: Option<Decimal>,
+
+       /// The total percentage that the spot price has moved by in `price_movement_epoch`
+       epoch_price_movement: Decimal,
+
+       /// The epoch that `epoch_price_movement` was accumulated in
+       price_movement_epoch: u64,
+
+       /// The first epoch that the pool may be traded in again after the epoch circuit breaker was tripped
+       halted_until_epoch: u64,
+
+       /// The configuration of the dynamic fee of the pool. When this is `None` the fee is always `fee_to_pool`
+       dynamic_fee: Option<DynamicFeeConfig>,
+
+       /// The epochs and the vault prices of the first sorted token after the most recent swaps, oldest first
+       trade_prices: Vec<(u64, Decimal)>,
+
+       /// The prices of the first and second sorted tokens as of the last update of the pool
+       last_price: (Decimal, Decimal),
+
+       /// The natural logarithm of the price of the first sorted token as of the last update of the pool
+       last_log_price: Decimal,
+
+       /// The liquidity of the pool, measured as `sqrt(x * y)`, as of the last update of the pool
+       last_liquidity: Decimal,
+
+       /// Ring buffer of the observations of the oracle accumulators. Used to calculate 
+       /// time weighted average prices and liquidity over past windows
+       observations: Vec<Observation>,
+
+       /// The index of the latest observation in the ring buffer
+       observation_index: u16,
+
+       /// The maximum number of observations that the ring buffer may hold
+       observation_cardinality: u16,
+
+       /// The resource address of the transient receipts of the flash loans
+       flash_loan_receipt_address: ResourceAddress,
+
+       /// The id of the next flash loan receipt to be minted
+       flash_loan_counter: u64,
+
+       /// The resource address of the transient receipts of the flash swaps
+       flash_swap_receipt_address: ResourceAddress,
+
+       /// The id of the next flash swap receipt to be minted
+       flash_swap_counter: u64,
+
+       /// The number of flash loans and flash swaps which have not been repaid or settled yet. While there are 
+       /// any outstanding the reserves of the pool are short, so swaps and changes of liquidity are not allowed
+       outstanding_flash_loans: u64
     }
 
     impl Pool {
@@ -31,7 +234,7 @@ mod pool {
         ///  - Check the two resource addresses are not the same
         ///  - Check resources are both fungible 
         ///  - Check the input token buckets are not empty
-        ///  - Check fee amount set is decimal between 0 to 100
+        ///  - Check fee amount set is decimal between 0 to 100, excluding 100
         /// Returns LP Tracking Token (for the initial liquidity provider
         /// Note: no change amount is returned as pool ratio is not established yet
         pub fn instantiate_pool(
@@ -60,10 +263,10 @@ mod pool {
                 "[Pool Creation]: Can't create a pool from an empty bucket."
             );
             
-            // Check fee amount set is decimal between 0 to 100
+            // Check fee amount set is decimal between 0 to 100, excluding 100 since no input would be left to swap with
             assert!(
-                (fee_to_pool >= Decimal::zero()) & (fee_to_pool <= dec!("100")), 
-                "[Pool Creation]: Fee must be between 0 and 100"
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")), 
+                "[Pool Creation]: Fee must be between 0 and 100, excluding 100"
             );                
 
             // Validation is done
@@ -85,6 +288,9 @@ mod pool {
             );
             
             let mut vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            let mut protocol_vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            protocol_vaults.insert(bucket1.resource_address(), Vault::new(bucket1.resource_address()));
+            protocol_vaults.insert(bucket2.resource_address(), Vault::new(bucket2.resource_address()));
             vaults.insert(bucket1.resource_address(), Vault::with_bucket(bucket1));
             vaults.insert(bucket2.resource_address(), Vault::with_bucket(bucket2));
 
@@ -108,79 +314,892 @@ mod pool {
                 .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
                 .mint_initial_supply(100);
 
-            // Creating the liquidity pool component and instantiating it
-            let liquidity_pool = Self { 
-                vaults: vaults,
-                tracking_token_address: tracking_tokens.resource_address(),
-                tracking_token_admin_badge: Vault::with_bucket(tracking_token_admin_badge),
-                fee_to_pool: fee_to_pool,
-            }
-            .instantiate()
-            // .globalize() NOTE: comment out if running manifests under `./manifests/pool` and using setup_pool_test.sh
-            ;
-            
-            return (liquidity_pool, tracking_tokens);
+            // Creating the non-fungible liquidity receipts which may be issued instead of the tracking tokens
+            let receipt_address: ResourceAddress = ResourceBuilder::new_integer_non_fungible()
+                .metadata("name", format!("{} LP Receipt", pair_name))
+                .metadata("symbol", "LPR")
+                .metadata("description", "A receipt recording the details of a deposit of liquidity into the liquidity pool")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .create_with_no_initial_supply();
+
+            // Creating the transient flash loan receipts. Denying all deposits of them forces the loans to be repaid 
+            // within the same transaction that they're taken out in.
+            let flash_loan_receipt_address: ResourceAddress = ResourceBuilder::new_integer_non_fungible()
+                .metadata("name", format!("{} Flash Loan Receipt", pair_name))
+                .metadata("symbol", "FLR")
+                .metadata("description", "A receipt of a flash loan which must be repaid within the same transaction")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .restrict_deposit(rule!(deny_all), LOCKED)
+                .create_with_no_initial_supply();
+
+            // Creating the transient flash swap receipts in the same way as the flash loan receipts
+            let flash_swap_receipt_address: ResourceAddress = ResourceBuilder::new_integer_non_fungible()
+                .metadata("name", format!("{} Flash Swap Receipt", pair_name))
+                .metadata("symbol", "FSR")
+                .metadata("description", "A receipt of a flash swap which must be settled within the same transaction")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .restrict_deposit(rule!(deny_all), LOCKED)
+                .create_with_no_initial_supply();
+
+            // The oracle starts from the initial values of the pool
+            let initial_price: Decimal = vaults[&addresses.1].amount() / vaults[&addresses.0].amount();
+            let initial_liquidity: Decimal = sqrt(vaults[&addresses.0].amount() * vaults[&addresses.1].amount());
+            let initial_observation: Observation = Observation {
+                epoch: Runtime::current_epoch(),
+                price_cumulative: (Decimal::zero(), Decimal::zero()),
+                log_price_cumulative: Decimal::zero(),
+                liquidity_cumulative: Decimal::zero()
+            };
+
+            // Creating the liquidity pool component and instantiating it
+            let liquidity_pool = Self { 
+                vaults: vaults,
+                tracking_token_address: tracking_tokens.resource_address(),
+                tracking_token_admin_badge: Vault::with_bucket(tracking_token_admin_badge),
+                fee_to_pool: fee_to_pool,
+                receipt_tracking_tokens: Vault::new(tracking_tokens.resource_address()),
+                receipt_address: receipt_address,
+                receipt_counter: 1,
+                protocol_fee: Decimal::zero(),
+                protocol_vaults: protocol_vaults,
+                paused: false,
+                max_price_movement: None,
+                max_epoch_price_movement: None,
+                epoch_price_movement: Decimal::zero(),
+                price_movement_epoch: Runtime::current_epoch(),
+                halted_until_epoch: 0,
+                dynamic_fee: None,
+                trade_prices: Vec::new(),
+                last_price: (initial_price, Decimal::one() / initial_price),
+                last_log_price: ln(initial_price),
+                last_liquidity: initial_liquidity,
+                observations: vec![initial_observation],
+                observation_index: 0,
+                observation_cardinality: DEFAULT_OBSERVATION_CARDINALITY,
+                flash_loan_receipt_address: flash_loan_receipt_address,
+                flash_loan_counter: 1,
+                flash_swap_receipt_address: flash_swap_receipt_address,
+                flash_swap_counter: 1,
+                outstanding_flash_loans: 0
+            }
+            .instantiate()
+            // .globalize() NOTE: comment out if running manifests under `./manifests/pool` and using setup_pool_test.sh
+            ;
+            
+            return (liquidity_pool, tracking_tokens);
+        }
+
+        /// Checks if the given address belongs to this pool or not.
+        /// 
+        /// This method is used to check if a given resource address belongs to one of the tokens in this liquidity pool
+        /// or not. A resource belongs to a liquidity pool if its address is in the addresses in the `vaults` HashMap.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `bool` - A boolean of whether the address belongs to this pool or not.
+        pub fn belongs_to_pool(
+            &self, 
+            address: ResourceAddress
+        ) -> bool {
+            return self.vaults.contains_key(&address);
+        }
+
+        /// Asserts that the given address belongs to the pool.
+        /// 
+        /// This is a quick assert method that checks if a given address belongs to the pool or not. If the address does
+        /// not belong to the pool, then an assertion error (panic) occurs and the message given is outputted.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// * `label` (String) - The label of the method that called this assert method. As an example, if the swap 
+        /// method were to call this method, then the label would be `Swap` so that it's clear where the assertion error
+        /// took place.
+        pub fn assert_belongs_to_pool(
+            &self, 
+            address: ResourceAddress, 
+            label: String
+        ) {
+            assert!(
+                self.belongs_to_pool(address), 
+                "[{}]: The provided resource address does not belong to the pool.", 
+                label
+            );
+        }
+
+        /// Gets the resource addresses of the tokens in this liquidity pool and returns them as a `Vec<ResourceAddress>`.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Vec<ResourceAddress>` - A vector of the resource addresses of the tokens in this liquidity pool.
+        pub fn addresses(&self) -> Vec<ResourceAddress> {
+            return self.vaults.keys().cloned().collect::<Vec<ResourceAddress>>();
+        }
+
+        /// Gets the name of the given liquidity pool from the symbols of the two tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// `String` - A string of the pair symbol
+        pub fn name(&self) -> String {
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return address_pair_symbol(addresses[0], addresses[1]);
+        }
+
+        /// Gets the resource address of the tracking tokens of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the tracking tokens.
+        pub fn tracking_token_address(&self) -> ResourceAddress {
+            return self.tracking_token_address;
+        }
+
+        /// Gets the resource address of the non-fungible liquidity receipts of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the liquidity receipts.
+        pub fn receipt_address(&self) -> ResourceAddress {
+            return self.receipt_address;
+        }
+
+        /// Gets the percentage fee paid to this liquidity pool on swaps.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the fee paid to the pool.
+        pub fn fee_to_pool(&self) -> Decimal {
+            return self.fee_to_pool;
+        }
+
+        /// Sets the percentage fee paid to this liquidity pool on swaps.
+        /// 
+        /// This method performs a number of checks before the fee is set:
+        /// 
+        /// * **Check 1:** Checks that the fee is a decimal between 0 and 100, excluding 100 since no input would be 
+        /// left to swap with.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `fee_to_pool` (Decimal) - The new percentage fee paid to the pool on swaps.
+        pub fn set_fee_to_pool(
+            &mut self,
+            fee_to_pool: Decimal
+        ) {
+            assert!(
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")), 
+                "[Set Fee]: Fee must be between 0 and 100, excluding 100"
+            );
+            info!("[Set Fee]: Fee set from {} to {}", self.fee_to_pool, fee_to_pool);
+
+            self.fee_to_pool = fee_to_pool;
+        }
+
+        /// Gets the configuration of the dynamic fee of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Option<DynamicFeeConfig>` - The configuration of the dynamic fee, or `None` if the fee is fixed.
+        pub fn dynamic_fee(&self) -> Option<DynamicFeeConfig> {
+            return self.dynamic_fee.clone();
+        }
+
+        /// Sets the configuration of the dynamic fee of this liquidity pool. Passing `None` goes back to the fixed fee
+        /// of `fee_to_pool`.
+        /// 
+        /// This method performs a number of checks before the dynamic fee is set:
+        /// 
+        /// * **Check 1:** Checks that the minimum fee is not negative and is not more than the maximum fee.
+        /// * **Check 2:** Checks that the maximum fee is less than 100, since no input would be left to swap with.
+        /// * **Check 3:** Checks that the volatility cap and the window are positive.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `dynamic_fee` (Option<DynamicFeeConfig>) - The new configuration of the dynamic fee.
+        pub fn set_dynamic_fee(
+            &mut self,
+            dynamic_fee: Option<DynamicFeeConfig>
+        ) {
+            if let Some(config) = &dynamic_fee {
+                assert!(
+                    (config.min_fee >= Decimal::zero()) & (config.min_fee <= config.max_fee),
+                    "[Set Dynamic Fee]: The minimum fee must be between 0 and the maximum fee."
+                );
+                assert!(config.max_fee < dec!("100"), "[Set Dynamic Fee]: The maximum fee must be less than 100.");
+                assert!(
+                    (config.volatility_cap > Decimal::zero()) & (config.window > 0),
+                    "[Set Dynamic Fee]: The volatility cap and the window must be positive."
+                );
+            }
+            info!("[Set Dynamic Fee]: Dynamic fee set to {:?}", dynamic_fee);
+
+            self.dynamic_fee = dynamic_fee;
+        }
+
+        /// Measures the volatility of the price of this liquidity pool from its trade history.
+        /// 
+        /// The volatility is the range of the prices after the swaps made within the window of the dynamic fee, as a 
+        /// percentage of the lowest of them. The volatility is zero when the pool has a fixed fee or when less than 
+        /// two swaps were made within the window.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The percentage volatility of the price of the pool.
+        pub fn volatility(&self) -> Decimal {
+            let window: u64 = match &self.dynamic_fee {
+                Some(config) => config.window,
+                None => return Decimal::zero()
+            };
+            let current_epoch: u64 = Runtime::current_epoch();
+            let start_epoch: u64 = if current_epoch > window { current_epoch - window } else { 0 };
+
+            let prices: Vec<Decimal> = self.trade_prices.iter()
+                .filter(|(epoch, _)| *epoch >= start_epoch)
+                .map(|(_, price)| *price)
+                .collect();
+            if prices.len() < 2 {
+                return Decimal::zero();
+            }
+            let min_price: Decimal = prices.iter()
+                .fold(prices[0], |min, price| if *price < min { *price } else { min });
+            let max_price: Decimal = prices.iter()
+                .fold(prices[0], |max, price| if *price > max { *price } else { max });
+            return (max_price - min_price) / min_price * dec!("100");
+        }
+
+        /// Gets the percentage fee currently paid to this liquidity pool on swaps, flash loans and flash swaps.
+        /// 
+        /// With a fixed fee this is `fee_to_pool`. With a dynamic fee this moves linearly from the minimum fee to the 
+        /// maximum fee as the volatility goes from zero to the volatility cap, and stays at the maximum fee beyond it.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - A decimal between 0 and 100 of the fee currently paid to the pool.
+        pub fn effective_fee(&self) -> Decimal {
+            return match &self.dynamic_fee {
+                Some(config) => {
+                    let volatility: Decimal = self.volatility();
+                    let ratio: Decimal = if volatility >= config.volatility_cap {
+                        Decimal::one()
+                    } else {
+                        volatility / config.volatility_cap
+                    };
+                    config.min_fee + (config.max_fee - config.min_fee) * ratio
+                },
+                None => self.fee_to_pool
+            };
+        }
+
+        /// Records the price of this liquidity pool after a swap into its trade history, dropping the oldest price when
+        /// the history is full.
+        fn record_trade_price(&mut self) {
+            if self.trade_prices.len() >= MAX_TRADE_HISTORY {
+                self.trade_prices.remove(0);
+            }
+            self.trade_prices.push((Runtime::current_epoch(), self.vault_price()));
+        }
+
+        /// Gets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the share of the swap fee paid to the protocol.
+        pub fn protocol_fee(&self) -> Decimal {
+            return self.protocol_fee;
+        }
+
+        /// Sets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// This method performs a number of checks before the protocol fee is set:
+        /// 
+        /// * **Check 1:** Checks that the protocol fee is a decimal between 0 and 100.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `protocol_fee` (Decimal) - The percentage of the swap fee to divert to the protocol.
+        pub fn set_protocol_fee(
+            &mut self,
+            protocol_fee: Decimal
+        ) {
+            assert!(
+                (protocol_fee >= Decimal::zero()) & (protocol_fee <= dec!("100")), 
+                "[Set Protocol Fee]: Protocol fee must be between 0 and 100"
+            );
+            info!("[Set Protocol Fee]: Protocol fee set from {} to {}", self.protocol_fee, protocol_fee);
+
+            self.protocol_fee = protocol_fee;
+        }
+
+        /// Withdraws all of the protocol fees collected by this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the protocol fees of each of the two tokens.
+        pub fn collect_protocol_fees(&mut self) -> Vec<Bucket> {
+            return self.protocol_vaults
+                .values_mut()
+                .map(|vault| vault.take_all())
+                .collect::<Vec<Bucket>>();
+        }
+
+        /// Gets whether this liquidity pool is paused or not.
+        /// 
+        /// # Returns:
+        /// 
+        /// `bool` - A boolean of whether the pool is paused.
+        pub fn paused(&self) -> bool {
+            return self.paused;
+        }
+
+        /// Pauses or unpauses this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `paused` (bool) - Whether the pool should be paused.
+        pub fn set_paused(
+            &mut self,
+            paused: bool
+        ) {
+            info!("[Set Paused]: Pool paused set from {} to {}", self.paused, paused);
+            self.paused = paused;
+        }
+
+        /// Asserts that this liquidity pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_paused(
+            &self,
+            label: String
+        ) {
+            assert!(!self.paused, "[{}]: The liquidity pool is paused.", label);
+        }
+
+        /// Gets the sorted resource addresses of the two tokens in this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `(ResourceAddress, ResourceAddress)` - The resource addresses of the two tokens sorted by `sort_addresses`.
+        fn sorted_addresses(&self) -> (ResourceAddress, ResourceAddress) {
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return sort_addresses(addresses[0], addresses[1]);
+        }
+
+        /// Gets the spot price of the first sorted token in terms of the second sorted token from the ratio of the two
+        /// vaults.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The ratio of the reserves of the second sorted token to the reserves of the first one.
+        fn vault_price(&self) -> Decimal {
+            let addresses: (ResourceAddress, ResourceAddress) = self.sorted_addresses();
+            return self.vaults[&addresses.1].amount() / self.vaults[&addresses.0].amount();
+        }
+
+        /// Gets the configuration of the circuit breaker of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Option<Decimal>` - The maximum percentage that a single swap may move the spot price by.
+        /// * `Option<Decimal>` - The maximum total percentage that the swaps within an epoch may move the spot price by.
+        pub fn circuit_breaker(&self) -> (Option<Decimal>, Option<Decimal>) {
+            return (self.max_price_movement, self.max_epoch_price_movement);
+        }
+
+        /// Configures the circuit breaker of this liquidity pool.
+        /// 
+        /// This method performs a number of checks before the circuit breaker is configured:
+        /// 
+        /// * **Check 1:** Checks that each of the maximum movements given is positive.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `max_price_movement` (Option<Decimal>) - The maximum percentage that a single swap may move the spot price
+        /// by, or `None` to disable the check.
+        /// * `max_epoch_price_movement` (Option<Decimal>) - The maximum total percentage that the swaps within an epoch
+        /// may move the spot price by, or `None` to disable the check.
+        pub fn set_circuit_breaker(
+            &mut self,
+            max_price_movement: Option<Decimal>,
+            max_epoch_price_movement: Option<Decimal>
+        ) {
+            for movement in [max_price_movement, max_epoch_price_movement].iter().flatten() {
+                assert!(
+                    *movement > Decimal::zero(), 
+                    "[Set Circuit Breaker]: The maximum price movements must be positive."
+                );
+            }
+            info!(
+                "[Set Circuit Breaker]: Max price movement per swap: {:?}, per epoch: {:?}", 
+                max_price_movement, max_epoch_price_movement
+            );
+
+            self.max_price_movement = max_price_movement;
+            self.max_epoch_price_movement = max_epoch_price_movement;
+        }
+
+        /// Checks the movement of the spot price caused by a swap against the circuit breaker.
+        /// 
+        /// This method is called after the swap has taken place. If the check fails, then the whole transaction fails 
+        /// and the swap is reverted. The movement of every swap is added to the total movement of the current epoch, 
+        /// and the swap which takes the total to `max_epoch_price_movement` or over it trips the epoch circuit breaker.
+        /// That swap goes through, since rejecting it would also revert the tripping of the breaker, and the pool is 
+        /// halted until the next epoch, which `assert_not_halted` enforces on the swaps that come after it.
+        /// 
+        /// This method performs a number of checks on the swap:
+        /// 
+        /// * **Check 1:** Checks that the price did not move by more than `max_price_movement` percent.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `price_before` (Decimal) - The vault price of the pool before the swap.
+        /// * `label` (String) - The label of the method that called this method.
+        fn check_circuit_breaker(
+            &mut self,
+            price_before: Decimal,
+            label: String
+        ) {
+            let price_movement: Decimal = (self.vault_price() - price_before).abs() / price_before * dec!("100");
+            if let Some(max_price_movement) = self.max_price_movement {
+                assert!(
+                    price_movement <= max_price_movement,
+                    "[{}]: Circuit breaker tripped, the swap moves the price by {}% which is more than {}%.",
+                    label, price_movement, max_price_movement
+                );
+            }
+
+            // Resetting the total movement when a new epoch starts
+            let current_epoch: u64 = Runtime::current_epoch();
+            if current_epoch != self.price_movement_epoch {
+                self.price_movement_epoch = current_epoch;
+                self.epoch_price_movement = Decimal::zero();
+            }
+            self.epoch_price_movement = self.epoch_price_movement + price_movement;
+            if let Some(max_epoch_price_movement) = self.max_epoch_price_movement {
+                if self.epoch_price_movement >= max_epoch_price_movement {
+                    info!(
+                        "[{}]: Circuit breaker tripped, the price has moved by {}% this epoch. Trading is halted until \
+                        the next epoch.",
+                        label, self.epoch_price_movement
+                    );
+                    self.halted_until_epoch = current_epoch + 1;
+                }
+            }
+        }
+
+        /// Gets whether the trading of this liquidity pool has been halted by the epoch circuit breaker or not.
+        /// 
+        /// # Returns:
+        /// 
+        /// `bool` - A boolean of whether trading is halted in the current epoch.
+        pub fn halted(&self) -> bool {
+            return Runtime::current_epoch() < self.halted_until_epoch;
+        }
+
+        /// Asserts that the trading of this liquidity pool has not been halted by the epoch circuit breaker.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_halted(
+            &self,
+            label: String
+        ) {
+            assert!(
+                !self.halted(),
+                "[{}]: Circuit breaker tripped, trading is halted until epoch {}.",
+                label, self.halted_until_epoch
+            );
+        }
+
+        /// Gets the current oracle values of this liquidity pool from its reserves.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `(Decimal, Decimal)` - The prices of the first and second sorted tokens.
+        /// * `Decimal` - The natural logarithm of the price of the first sorted token.
+        /// * `Decimal` - The liquidity of the pool, measured as `sqrt(x * y)`.
+        fn oracle_values(&self) -> ((Decimal, Decimal), Decimal, Decimal) {
+            let price: Decimal = self.vault_price();
+            return ((price, Decimal::one() / price), ln(price), sqrt(self.k()));
+        }
+
+        /// Extends the given observation to a later epoch using the values that the pool has been at since the last
+        /// update.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `observation` (&Observation) - The observation to extend, which is the latest one.
+        /// * `epoch` (u64) - The epoch to extend the observation to.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Observation` - The value of the accumulators at the given epoch.
+        fn extrapolate(
+            &self,
+            observation: &Observation,
+            epoch: u64
+        ) -> Observation {
+            let elapsed: Decimal = Decimal::from(epoch - observation.epoch);
+            return Observation {
+                epoch: epoch,
+                price_cumulative: (
+                    observation.price_cumulative.0 + self.last_price.0 * elapsed,
+                    observation.price_cumulative.1 + self.last_price.1 * elapsed
+                ),
+                log_price_cumulative: observation.log_price_cumulative + self.last_log_price * elapsed,
+                liquidity_cumulative: observation.liquidity_cumulative + self.last_liquidity * elapsed
+            };
+        }
+
+        /// Updates the oracle of this liquidity pool.
+        /// 
+        /// This method is called after every swap and change of liquidity. On the first update in an epoch, the values
+        /// that the pool has been at since the last update are added to the accumulators for every epoch that has
+        /// passed since then, and the result is written as a new observation into the ring buffer. The current values
+        /// of the pool are then recorded as the values that it stays at until the next update.
+        fn update_oracle(&mut self) {
+            let current_epoch: u64 = Runtime::current_epoch();
+            let latest: Observation = self.observations[self.observation_index as usize].clone();
+            if current_epoch > latest.epoch {
+                let observation: Observation = self.extrapolate(&latest, current_epoch);
+                self.write_observation(observation);
+            }
+
+            // The values are left as they are when the pool has been emptied out since they can not be calculated
+            let addresses: (ResourceAddress, ResourceAddress) = self.sorted_addresses();
+            if !self.vaults[&addresses.0].is_empty() & !self.vaults[&addresses.1].is_empty() {
+                let (price, log_price, liquidity): ((Decimal, Decimal), Decimal, Decimal) = self.oracle_values();
+                self.last_price = price;
+                self.last_log_price = log_price;
+                self.last_liquidity = liquidity;
+            }
+        }
+
+        /// Writes a new observation into the ring buffer of observations.
+        /// 
+        /// The buffer only grows when the latest observation is at its end and the cardinality allows for more
+        /// observations. Otherwise the oldest observation is overwritten. This keeps the observations in chronological
+        /// order starting from the one after the latest.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `observation` (Observation) - The observation to write.
+        fn write_observation(
+            &mut self,
+            observation: Observation
+        ) {
+            let length: u16 = self.observations.len() as u16;
+            if (self.observation_index == length - 1) & (length < self.observation_cardinality) {
+                self.observations.push(observation);
+                self.observation_index = length;
+            } else {
+                self.observation_index = (self.observation_index + 1) % length;
+                self.observations[self.observation_index as usize] = observation;
+            }
+        }
+
+        /// Finds the value of the oracle accumulators at the given epoch.
+        /// 
+        /// Between two observations the pool stayed at the values of its last update in the epoch of the earlier
+        /// observation, so the accumulators grew linearly and their value at any epoch in between is found by
+        /// interpolating between the two observations. After the latest observation the accumulators are extended with
+        /// the values of the last update.
+        /// 
+        /// This method performs a number of checks before the accumulators are found:
+        /// 
+        /// * **Check 1:** Checks that the epoch is not in the future.
+        /// * **Check 2:** Checks that the epoch is not before the oldest observation in the ring buffer.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `epoch` (u64) - The epoch to find the value of the accumulators at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Observation` - The value of the accumulators at the given epoch.
+        pub fn observe(
+            &self,
+            epoch: u64
+        ) -> Observation {
+            assert!(epoch <= Runtime::current_epoch(), "[Observe]: Can not observe an epoch in the future.");
+
+            let latest: &Observation = &self.observations[self.observation_index as usize];
+            if epoch >= latest.epoch {
+                return self.extrapolate(latest, epoch);
+            }
+
+            let length: usize = self.observations.len();
+            let oldest_index: usize = (self.observation_index as usize + 1) % length;
+            let mut before: &Observation = &self.observations[oldest_index];
+            assert!(
+                epoch >= before.epoch,
+                "[Observe]: Not enough history, the oldest observation is from epoch {}.",
+                before.epoch
+            );
+
+            // Walking the observations in chronological order until the first one after the epoch is found. This is
+            // always found as the latest observation is after the epoch.
+            for i in 1..length {
+                let after: &Observation = &self.observations[(oldest_index + i) % length];
+                if after.epoch > epoch {
+                    return interpolate(before, after, epoch);
+                }
+                before = after;
+            }
+            panic!("[Observe]: The observations are out of order.");
+        }
+
+        /// Gets the number of observations stored in the ring buffer and the number of observations that it may grow to.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `u16` - The number of observations currently stored.
+        /// * `u16` - The cardinality of the ring buffer.
+        pub fn observation_cardinality(&self) -> (u16, u16) {
+            return (self.observations.len() as u16, self.observation_cardinality);
+        }
+
+        /// Grows the cardinality of the ring buffer of observations so that the oracle keeps more history.
+        /// 
+        /// The new observations are added as the pool is updated in new epochs, so the history becomes available over
+        /// time rather than immediately.
+        /// 
+        /// This method performs a number of checks before the cardinality is grown:
+        /// 
+        /// * **Check 1:** Checks that the new cardinality is larger than the current one.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `cardinality` (u16) - The new maximum number of observations to store.
+        pub fn grow_observations(
+            &mut self,
+            cardinality: u16
+        ) {
+            assert!(
+                cardinality > self.observation_cardinality,
+                "[Grow Observations]: The cardinality can only grow, it is currently {}.",
+                self.observation_cardinality
+            );
+            info!("[Grow Observations]: Cardinality grown from {} to {}", self.observation_cardinality, cardinality);
+
+            self.observation_cardinality = cardinality;
+        }
+
+        /// Observes the oracle at the start and the end of the given window.
+        /// 
+        /// This method performs a number of checks before the oracle is observed:
+        /// 
+        /// * **Check 1:** Checks that the start of the window is before its end.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// * `label` (String) - The label of the method that called this method.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Observation` - The value of the accumulators at the start of the window.
+        /// * `Observation` - The value of the accumulators at the end of the window.
+        /// * `Decimal` - The length of the window in epochs.
+        fn observe_window(
+            &self,
+            start_epoch: u64,
+            end_epoch: u64,
+            label: String
+        ) -> (Observation, Observation, Decimal) {
+            assert!(start_epoch < end_epoch, "[{}]: The start of the window must be before its end.", label);
+
+            return (self.observe(start_epoch), self.observe(end_epoch), Decimal::from(end_epoch - start_epoch));
+        }
+
+        /// Calculates the time weighted arithmetic mean price of the given token between two epochs.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The average amount of the other token that one of the given token was worth over the window.
+        pub fn twap_between(
+            &self,
+            resource_address: ResourceAddress,
+            start_epoch: u64,
+            end_epoch: u64
+        ) -> Decimal {
+            self.assert_belongs_to_pool(resource_address, String::from("TWAP"));
+
+            let (start, end, window): (Observation, Observation, Decimal) = self.observe_window(
+                start_epoch, end_epoch, String::from("TWAP")
+            );
+            return if resource_address == self.sorted_addresses().0 {
+                (end.price_cumulative.0 - start.price_cumulative.0) / window
+            } else {
+                (end.price_cumulative.1 - start.price_cumulative.1) / window
+            };
+        }
+
+        /// Calculates the time weighted geometric mean price of the given token between two epochs.
+        /// 
+        /// Unlike the arithmetic mean, the geometric mean price of one token is exactly the inverse of the geometric
+        /// mean price of the other, and it is less affected by short lived spikes in the price.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The geometric mean of the amount of the other token that one of the given token was worth over
+        /// the window.
+        pub fn geometric_twap_between(
+            &self,
+            resource_address: ResourceAddress,
+            start_epoch: u64,
+            end_epoch: u64
+        ) -> Decimal {
+            self.assert_belongs_to_pool(resource_address, String::from("Geometric TWAP"));
+
+            let (start, end, window): (Observation, Observation, Decimal) = self.observe_window(
+                start_epoch, end_epoch, String::from("Geometric TWAP")
+            );
+            let mean_log_price: Decimal = (end.log_price_cumulative - start.log_price_cumulative) / window;
+            return if resource_address == self.sorted_addresses().0 {
+                exp(mean_log_price)
+            } else {
+                exp(Decimal::zero() - mean_log_price)
+            };
+        }
+
+        /// Calculates the time weighted average liquidity of this liquidity pool between two epochs.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `start_epoch` (u64) - The epoch that the window starts at.
+        /// * `end_epoch` (u64) - The epoch that the window ends at.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The average liquidity of the pool, measured as `sqrt(x * y)`, over the window.
+        pub fn average_liquidity_between(
+            &self,
+            start_epoch: u64,
+            end_epoch: u64
+        ) -> Decimal {
+            let (start, end, window): (Observation, Observation, Decimal) = self.observe_window(
+                start_epoch, end_epoch, String::from("Average Liquidity")
+            );
+            return (end.liquidity_cumulative - start.liquidity_cumulative) / window;
         }
 
-        /// Checks if the given address belongs to this pool or not.
+        /// Calculates the time weighted average price of the given token over the given number of past epochs.
         /// 
-        /// This method is used to check if a given resource address belongs to one of the tokens in this liquidity pool
-        /// or not. A resource belongs to a liquidity pool if its address is in the addresses in the `vaults` HashMap.
+        /// The price of a token is weighted by the number of epochs that the pool stayed at it, which makes the average
+        /// price resistant to manipulation as moving it requires keeping the price of the pool away from the market
+        /// price for many epochs.
+        /// 
+        /// This method performs a number of checks before the average price is calculated:
+        /// 
+        /// * **Check 1:** Checks that the resource address given does indeed belong to this liquidity pool.
+        /// * **Check 2:** Checks that the window is at least one epoch.
+        /// * **Check 3:** Checks that the oracle has observations going back to the start of the window.
         /// 
         /// # Arguments:
         /// 
-        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the average price of.
+        /// * `window` (u64) - The number of epochs to average the price over, ending at the current epoch.
         /// 
         /// # Returns:
         /// 
-        /// * `bool` - A boolean of whether the address belongs to this pool or not.
-        pub fn belongs_to_pool(
-            &self, 
-            address: ResourceAddress
-        ) -> bool {
-            return self.vaults.contains_key(&address);
+        /// * `Decimal` - The average amount of the other token that one of the given token was worth over the window.
+        pub fn twap(
+            &self,
+            resource_address: ResourceAddress,
+            window: u64
+        ) -> Decimal {
+            let current_epoch: u64 = Runtime::current_epoch();
+            assert!(window <= current_epoch, "[TWAP]: Not enough price history for the given window.");
+
+            return self.twap_between(resource_address, current_epoch - window, current_epoch);
         }
 
-        /// Asserts that the given address belongs to the pool.
-        /// 
-        /// This is a quick assert method that checks if a given address belongs to the pool or not. If the address does
-        /// not belong to the pool, then an assertion error (panic) occurs and the message given is outputted.
+        /// Asserts that there are no outstanding flash loans from this liquidity pool.
         /// 
         /// # Arguments:
         /// 
-        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
-        /// * `label` (String) - The label of the method that called this assert method. As an example, if the swap 
-        /// method were to call this method, then the label would be `Swap` so that it's clear where the assertion error
-        /// took place.
-        pub fn assert_belongs_to_pool(
-            &self, 
-            address: ResourceAddress, 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_no_flash_loan(
+            &self,
             label: String
         ) {
             assert!(
-                self.belongs_to_pool(address), 
-                "[{}]: The provided resource address does not belong to the pool.", 
+                self.outstanding_flash_loans == 0, 
+                "[{}]: Not allowed while a flash loan from the liquidity pool is outstanding.", 
                 label
             );
         }
 
-        /// Gets the resource addresses of the tokens in this liquidity pool and returns them as a `Vec<ResourceAddress>`.
+        /// Takes the share of the swap fee which goes to the protocol out of the given input tokens.
         /// 
-        /// # Returns:
+        /// The swap fee is `effective_fee` percent of the input and the protocol takes `protocol_fee` percent of that. 
+        /// Since the output of a swap is calculated only from the part of the input left after the fee, the output is 
+        /// not affected by this. The only difference is that less of the fee is deposited into the pool for the 
+        /// liquidity providers.
         /// 
-        /// `Vec<ResourceAddress>` - A vector of the resource addresses of the tokens in this liquidity pool.
-        pub fn addresses(&self) -> Vec<ResourceAddress> {
-            return self.vaults.keys().cloned().collect::<Vec<ResourceAddress>>();
+        /// # Arguments:
+        /// 
+        /// * `tokens` (&mut Bucket) - A bucket of the input tokens of a swap.
+        fn take_protocol_fee(
+            &mut self,
+            tokens: &mut Bucket
+        ) {
+            let protocol_amount: Decimal = tokens.amount() * self.effective_fee() / dec!("100") 
+                * self.protocol_fee / dec!("100");
+            if protocol_amount > Decimal::zero() {
+                info!("[Protocol Fee]: Taking {} of {:?} as protocol fee", protocol_amount, tokens.resource_address());
+                self.protocol_vaults.get_mut(&tokens.resource_address()).unwrap().put(tokens.take(protocol_amount));
+            }
         }
 
-        /// Gets the name of the given liquidity pool from the symbols of the two tokens.
+        /// Gets the amount of reserves of the given token in this liquidity pool.
+        /// 
+        /// This method performs a number of checks before the reserves are obtained:
+        /// 
+        /// * **Check 1:** Checks that the resource address given does indeed belong to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the reserves of.
         /// 
         /// # Returns:
         /// 
-        /// `String` - A string of the pair symbol
-        pub fn name(&self) -> String {
-            let addresses: Vec<ResourceAddress> = self.addresses();
-            return address_pair_symbol(addresses[0], addresses[1]);
+        /// `Decimal` - The amount of the given token in the vaults of this liquidity pool.
+        pub fn reserve(
+            &self,
+            resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(resource_address, String::from("Reserve"));
+
+            return self.vaults[&resource_address].amount();
         }
 
         /// This method takes in a resource address and if this resource address belongs to the pool it returns the 
@@ -219,6 +1238,32 @@ mod pool {
             return self.vaults[&addresses[0]].amount() * self.vaults[&addresses[1]].amount()
         }
 
+        /// Calculates the spot price of the given token in terms of the other token in this liquidity pool.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that the provided resource address belongs to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the token to get the price of.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of the other token that one input token is worth at the current reserves, not 
+        /// taking the fee or the price impact into account. This is `y / x` in the constant market maker function.
+        pub fn spot_price(
+            &self,
+            input_resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(input_resource_address, String::from("Spot Price"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&self.other_resource_address(input_resource_address)].amount();
+            return y / x;
+        }
+
         /// This method calculates the amount of output tokens that would be received for a given amount of an input
         /// token. This is calculated through the constant market maker function `x * y = k`. 
         /// 
@@ -256,7 +1301,7 @@ mod pool {
             let x: Decimal = self.vaults[&input_resource_address].amount();
             let y: Decimal = self.vaults[&self.other_resource_address(input_resource_address)].amount();
             let dx: Decimal = input_amount;
-            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+            let r: Decimal = (dec!("100") - self.effective_fee()) / dec!("100");
 
             let dy: Decimal = (dx * r * y) / ( x + r * dx );
             return dy;
@@ -299,7 +1344,7 @@ mod pool {
             let x: Decimal = self.vaults[&self.other_resource_address(output_resource_address)].amount();
             let y: Decimal = self.vaults[&output_resource_address].amount();
             let dy: Decimal = output_amount;
-            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+            let r: Decimal = (dec!("100") - self.effective_fee()) / dec!("100");
 
             let dx: Decimal = (dy * x) / (r * (y - dy));
             return dx;
@@ -371,8 +1416,10 @@ mod pool {
         /// 
         /// This method performs a number of checks before liquidity is added to the pool:
         /// 
-        /// * **Check 1:** Checks that the buckets passed are of tokens that belong to this liquidity pool.
-        /// * **Check 2:** Checks that the buckets passed are not empty.
+        /// * **Check 1:** Checks that the liquidity pool is not paused.
+        /// * **Check 2:** Checks that the buckets passed are of tokens that belong to this liquidity pool.
+        /// * **Check 3:** Checks that the buckets passed are not empty.
+        /// * **Check 4:** Checks that the amounts deposited and the tracking tokens minted are not below the minimums.
         /// 
         /// From the perspective of adding liquidity, these are all of the checks that need to be done. The Pool 
         /// component does not need to perform any additional checks when liquidity is being added.
@@ -381,6 +1428,10 @@ mod pool {
         /// 
         /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
         /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
         /// 
         /// # Returns:
         /// 
@@ -412,8 +1463,13 @@ mod pool {
             &mut self,
             token1: Bucket,
             token2: Bucket,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal
         ) -> (Bucket, Bucket, Bucket) {
-            // Checking if the tokens belong to this liquidity pool.
+            // Checking that the pool is not paused or in a flash loan and that the tokens belong to this liquidity pool.
+            self.assert_not_paused(String::from("Add Liquidity"));
+            self.assert_no_flash_loan(String::from("Add Liquidity"));
             self.assert_belongs_to_pool(token1.resource_address(), String::from("Add Liquidity"));
             self.assert_belongs_to_pool(token2.resource_address(), String::from("Add Liquidity"));
 
@@ -425,7 +1481,14 @@ mod pool {
                 token1.resource_address(), token1.amount(), token2.resource_address(), token2.amount()
             );
 
-            // Sorting out the two buckets passed and getting the values of `dm` and `dn`.
+            // Sorting out the two buckets passed and getting the values of `dm` and `dn`. The minimum deposits are 
+            // sorted along with the buckets.
+            let sorted: bool = token1.resource_address() == self.sorted_addresses().0;
+            let (min_amount1, min_amount2): (Decimal, Decimal) = if sorted {
+                (min_token1_deposit, min_token2_deposit)
+            } else {
+                (min_token2_deposit, min_token1_deposit)
+            };
             let (mut bucket1, mut bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
             let dm: Decimal = bucket1.amount();
             let dn: Decimal = bucket2.amount();
@@ -453,6 +1516,10 @@ mod pool {
                 "[Add Liquidity]: Liquidity amount to add: {:?}: {}, {:?}: {}", 
                 bucket1.resource_address(), amount1, bucket2.resource_address(), amount2
             );
+            assert!(
+                (amount1 >= min_amount1) & (amount2 >= min_amount2),
+                "[Add Liquidity]: The amounts deposited are below the minimums. The reserve ratio may have moved."
+            );
 
             // Depositing the amount of tokens calculated into the liquidity pool
             self.deposit(bucket1.take(amount1));
@@ -471,11 +1538,113 @@ mod pool {
                 tracking_tokens_manager.mint(tracking_amount)
             });
             info!("[Add Liquidity]: Owed amount of tracking tokens: {}", tracking_amount);
+            assert!(
+                tracking_amount >= min_tracking_tokens_out,
+                "[Add Liquidity]: Tracking tokens minted ({}) are below the minimum of {}.",
+                tracking_amount, min_tracking_tokens_out
+            );
 
             // Returning the remaining tokens from `token1`, `token2`, and the tracking tokens
+            self.update_oracle();
             return (bucket1, bucket2, tracking_tokens);
         }
 
+        /// Adds liquidity to this liquidity pool in exchange for a non-fungible liquidity receipt.
+        /// 
+        /// This method adds liquidity in exactly the same way as `add_liquidity`. However, instead of returning the 
+        /// tracking tokens to the liquidity provider, the tracking tokens are kept in the liquidity pool and a receipt
+        /// is issued which records the amounts deposited, the epoch of the deposit, the pool, and the amount of 
+        /// tracking tokens which the receipt is worth. The receipt may later be given to `remove_liquidity` in place 
+        /// of the tracking tokens.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens the receipt must be worth.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the first sorted token.
+        /// * `Bucket` - A bucket of the remaining tokens of the second sorted token.
+        /// * `Bucket` - A bucket of the liquidity receipt issued to the liquidity provider.
+        pub fn add_liquidity_with_receipt(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal
+        ) -> (Bucket, Bucket, Bucket) {
+            // Sorting the buckets so that the amounts deposited can be found from what remains of them, along with the
+            // minimum deposits of each of them
+            let sorted: bool = token1.resource_address() == self.sorted_addresses().0;
+            let (min_amount1, min_amount2): (Decimal, Decimal) = if sorted {
+                (min_token1_deposit, min_token2_deposit)
+            } else {
+                (min_token2_deposit, min_token1_deposit)
+            };
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address());
+            let amounts: (Decimal, Decimal) = (bucket1.amount(), bucket2.amount());
+
+            let (remaining1, remaining2, tracking_tokens): (Bucket, Bucket, Bucket) = self.add_liquidity(
+                bucket1, bucket2, min_tracking_tokens_out, min_amount1, min_amount2
+            );
+            let receipt_data: LiquidityReceipt = LiquidityReceipt {
+                lp_id: format!("{:?}-{:?}", addresses.0, addresses.1),
+                addresses: addresses,
+                deposit_amounts: (amounts.0 - remaining1.amount(), amounts.1 - remaining2.amount()),
+                deposit_epoch: Runtime::current_epoch(),
+                tracking_amount: tracking_tokens.amount()
+            };
+            info!(
+                "[Add Liquidity With Receipt]: Issuing receipt #{} for deposit of {:?} worth {} tracking tokens", 
+                self.receipt_counter, receipt_data.deposit_amounts, receipt_data.tracking_amount
+            );
+
+            // Keeping the tracking tokens in the pool and minting the receipt of the deposit
+            self.receipt_tracking_tokens.put(tracking_tokens);
+            let receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(self.receipt_counter);
+            self.receipt_counter = self.receipt_counter + 1;
+
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.receipt_address);
+            let receipt: Bucket = self.tracking_token_admin_badge.authorize(|| {
+                receipt_manager.mint_non_fungible(&receipt_id, receipt_data)
+            });
+
+            return (remaining1, remaining2, receipt);
+        }
+
+        /// Burns the given liquidity receipts and returns the tracking tokens that they are worth.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `receipts` (Bucket) - A bucket of liquidity receipts of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the tracking tokens held by the pool on behalf of the receipts.
+        fn redeem_receipts(
+            &mut self,
+            receipts: Bucket
+        ) -> Bucket {
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.receipt_address);
+            let tracking_amount: Decimal = receipts.non_fungible_local_ids()
+                .iter()
+                .map(|id| receipt_manager.get_non_fungible_data::<LiquidityReceipt>(id).tracking_amount)
+                .fold(Decimal::zero(), |total, amount| total + amount);
+            info!("[Redeem Receipts]: Redeeming {} receipts for {} tracking tokens", receipts.amount(), tracking_amount);
+
+            self.tracking_token_admin_badge.authorize(|| {
+                receipts.burn();
+            });
+
+            return self.receipt_tracking_tokens.take(tracking_amount);
+        }
+
         /// Removes the percentage of the liquidity owed to this liquidity provider.
         /// 
         /// This method is used to calculate the amount of tokens owed to the liquidity provider and take them out of
@@ -488,20 +1657,42 @@ mod pool {
         /// This method performs a number of checks before liquidity removed from the pool:
         /// 
         /// * **Check 1:** Checks to ensure that the tracking tokens passed do indeed belong to this liquidity pool.
+        /// * **Check 2:** Checks that the amounts withdrawn are not below the minimums.
+        /// 
+        /// Liquidity receipts issued by `add_liquidity_with_receipt` may be passed instead of the tracking tokens, in
+        /// which case the receipts are burned and the tracking tokens that they are worth are removed instead.
+        /// 
+        /// The minimums follow the order of the two tokens as sorted by `sort_addresses`, which is also the order that
+        /// the two buckets are returned in.
         /// 
         /// # Arguments:
         /// 
-        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to 
-        /// exchange for their share of the liquidity.
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens or liquidity receipts that the liquidity 
+        /// provider wishes to exchange for their share of the liquidity.
+        /// * `min_amount1` (Decimal) - The minimum amount of the first sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// * `min_amount2` (Decimal) - The minimum amount of the second sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
         /// 
         /// # Returns:
         /// 
-        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first token.
-        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second token.
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first sorted token.
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second sorted token.
         pub fn remove_liquidity(
             &mut self,
-            tracking_tokens: Bucket
+            tracking_tokens: Bucket,
+            min_amount1: Decimal,
+            min_amount2: Decimal
         ) -> (Bucket, Bucket) {
+            self.assert_no_flash_loan(String::from("Remove Liquidity"));
+
+            // Exchanging liquidity receipts for the tracking tokens which they are worth
+            let tracking_tokens: Bucket = if tracking_tokens.resource_address() == self.receipt_address {
+                self.redeem_receipts(tracking_tokens)
+            } else {
+                tracking_tokens
+            };
+
             // Checking the resource address of the tracking tokens passed to ensure that they do indeed belong to this
             // liquidity pool.
             assert_eq!(
@@ -521,10 +1712,17 @@ mod pool {
             });
 
             // Withdrawing the amount of tokens owed to this liquidity provider
-            let addresses: Vec<ResourceAddress> = self.addresses();
-            let bucket1: Bucket = self.withdraw(addresses[0], self.vaults[&addresses[0]].amount() * percentage);
-            let bucket2: Bucket = self.withdraw(addresses[1], self.vaults[&addresses[1]].amount() * percentage);
+            let addresses: (ResourceAddress, ResourceAddress) = self.sorted_addresses();
+            let bucket1: Bucket = self.withdraw(addresses.0, self.vaults[&addresses.0].amount() * percentage);
+            let bucket2: Bucket = self.withdraw(addresses.1, self.vaults[&addresses.1].amount() * percentage);
+            assert!(
+                (bucket1.amount() >= min_amount1) & (bucket2.amount() >= min_amount2),
+                "[Remove Liquidity]: The amounts withdrawn ({}, {}) are below the minimums. The reserves may have \
+                moved.",
+                bucket1.amount(), bucket2.amount()
+            );
 
+            self.update_oracle();
             return (bucket1, bucket2);
         }
 
@@ -534,11 +1732,15 @@ mod pool {
         /// that does not perform a lot of checks on the tokens being swapped, slippage, or things of that sort. It is
         /// up to the caller of the this method (typically another method / function) to perform the checks needed. 
         /// When swaps are performed through this method, the associated fee of the pool is taken when this swap method
-        /// is called.
+        /// is called. The share of the fee set by `protocol_fee` is diverted into the protocol vaults.
         /// 
         /// This method performs a number of checks before the swap is performed:
         /// 
-        /// * **Check 1:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// * **Check 1:** Checks that the liquidity pool is not paused or halted by the epoch circuit breaker.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// 
+        /// Once the swap is performed, the movement of the spot price is checked against the circuit breaker through 
+        /// `check_circuit_breaker`.
         /// 
         /// # Arguments:
         /// 
@@ -549,9 +1751,12 @@ mod pool {
         /// * `Bucket` - A bucket of the other tokens.
         pub fn swap(
             &mut self,
-            tokens: Bucket
+            mut tokens: Bucket
         ) -> Bucket {
-            // Checking if the tokens belong to this liquidity pool.
+            // Checking that the pool is not paused or in a flash loan and that the tokens belong to this liquidity pool.
+            self.assert_not_paused(String::from("Swap"));
+            self.assert_not_halted(String::from("Swap"));
+            self.assert_no_flash_loan(String::from("Swap"));
             self.assert_belongs_to_pool(tokens.resource_address(), String::from("Swap"));
 
             // For debugging purposes, get current vault reserves
@@ -566,6 +1771,7 @@ mod pool {
             );
             
             info!("[Swap]: K before swap: {}", self.k());
+            let price_before: Decimal = self.vault_price();
 
             // Calculating the output amount for the given input amount of tokens and withdrawing it from the vault
             let output_amount: Decimal = self.calculate_output_amount(tokens.resource_address(), tokens.amount());
@@ -575,9 +1781,14 @@ mod pool {
                 output_amount
             );
 
-            // Depositing the tokens into the liquidity pool and returning a bucket of the swapped tokens.
+            // Depositing the tokens, less the protocol's share of the fee, into the liquidity pool and returning a 
+            // bucket of the swapped tokens.
+            self.take_protocol_fee(&mut tokens);
             self.deposit(tokens);
             info!("[Swap]: K after swap: {}", self.k());
+            self.check_circuit_breaker(price_before, String::from("Swap"));
+            self.record_trade_price();
+            self.update_oracle();
             return output_tokens;
         }
 
@@ -591,7 +1802,8 @@ mod pool {
         /// 
         /// This method performs a number of checks before the swap is performed:
         /// 
-        /// * **Check 1:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// * **Check 1:** Checks that the liquidity pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
         /// 
         /// # Arguments:
         /// 
@@ -607,7 +1819,10 @@ mod pool {
             tokens: Bucket,
             min_amount_out: Decimal
         ) -> Bucket {
-            // Checking that the bucket passed does indeed belong to this liquidity pool
+            // Checking that the pool is not paused and that the bucket passed does indeed belong to this liquidity pool
+            self.assert_not_paused(String::from("Swap Exact"));
+            self.assert_not_halted(String::from("Swap Exact"));
+            self.assert_no_flash_loan(String::from("Swap Exact"));
             self.assert_belongs_to_pool(tokens.resource_address(), String::from("Swap Exact"));
             
             // Performing the token swap and checking if the amount is suitable for the caller or not. This is one of 
@@ -631,7 +1846,8 @@ mod pool {
         /// 
         /// This method performs a number of checks before the swap is performed:
         /// 
-        /// * **Check 1:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// * **Check 1:** Checks that the liquidity pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
         /// 
         /// # Arguments:
         /// 
@@ -648,7 +1864,10 @@ mod pool {
             mut tokens: Bucket,
             output_amount: Decimal
         ) -> (Bucket, Bucket) {
-            // Checking that the bucket passed does indeed belong to this liquidity pool
+            // Checking that the pool is not paused and that the bucket passed does indeed belong to this liquidity pool
+            self.assert_not_paused(String::from("Swap For Exact"));
+            self.assert_not_halted(String::from("Swap For Exact"));
+            self.assert_no_flash_loan(String::from("Swap For Exact"));
             self.assert_belongs_to_pool(tokens.resource_address(), String::from("Swap For Exact"));
 
             // Calculating the amount of input tokens that would be required to produce the desired amount of output 
@@ -665,14 +1884,275 @@ mod pool {
 
             // Depositing the amount of input required into the vaults and taking out the requested amount
             info!("[Swap For Exact]: K before swap: {}", self.k());
-            self.deposit(tokens.take(input_required));
+            let price_before: Decimal = self.vault_price();
+            let mut input_tokens: Bucket = tokens.take(input_required);
+            self.take_protocol_fee(&mut input_tokens);
+            self.deposit(input_tokens);
             let output_tokens: Bucket = self.withdraw(
                 self.other_resource_address(tokens.resource_address()), 
                 output_amount
             );
             info!("[Swap For Exact]: K after swap: {}", self.k());
+            self.check_circuit_breaker(price_before, String::from("Swap For Exact"));
+            self.record_trade_price();
+            self.update_oracle();
             info!("[Swap For Exact]: Amount gievn out: {}", output_tokens.amount());
             return (output_tokens, tokens);
         }
+
+        /// Gets the resource address of the transient receipts of the flash loans of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the flash loan receipts.
+        pub fn flash_loan_receipt_address(&self) -> ResourceAddress {
+            return self.flash_loan_receipt_address;
+        }
+
+        /// Lends tokens from the reserves of this liquidity pool which must be repaid within the same transaction.
+        /// 
+        /// Along with the borrowed tokens, a transient receipt is returned which can not be deposited into any account
+        /// or component. Since a transaction can not end while there are buckets left on the worktop, the only way for
+        /// the transaction to succeed is to give the receipt back to `repay_flash_loan` along with the amount borrowed
+        /// and the fee. The fee is `effective_fee` percent of the amount borrowed and goes to the liquidity providers. 
+        /// 
+        /// This method performs a number of checks before the loan is given:
+        /// 
+        /// * **Check 1:** Checks that the liquidity pool is not paused or in another flash loan or flash swap.
+        /// * **Check 2:** Checks that the trading of the liquidity pool has not been halted by the circuit breaker.
+        /// * **Check 3:** Checks that the resource address given does indeed belong to this liquidity pool.
+        /// * **Check 4:** Checks that the amount to borrow is positive.
+        /// * **Check 5:** Checks that the there is enough liquidity to lend the amount.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the tokens to borrow.
+        /// * `amount` (Decimal) - The amount of tokens to borrow.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the borrowed tokens.
+        /// * `Bucket` - A bucket of the transient receipt of the flash loan.
+        pub fn flash_loan(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal
+        ) -> (Bucket, Bucket) {
+            self.assert_not_paused(String::from("Flash Loan"));
+            self.assert_not_halted(String::from("Flash Loan"));
+            self.assert_no_flash_loan(String::from("Flash Loan"));
+            self.assert_belongs_to_pool(resource_address, String::from("Flash Loan"));
+            assert!(amount > Decimal::zero(), "[Flash Loan]: The amount to borrow must be positive.");
+
+            let receipt_data: FlashLoanReceipt = FlashLoanReceipt {
+                resource_address: resource_address,
+                amount: amount,
+                fee: amount * self.effective_fee() / dec!("100")
+            };
+            info!("[Flash Loan]: Lending {} of {:?} for a fee of {}", amount, resource_address, receipt_data.fee);
+
+            // Taking the tokens out of the pool and minting the receipt of the loan
+            let tokens: Bucket = self.withdraw(resource_address, amount);
+            let receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(self.flash_loan_counter);
+            self.flash_loan_counter = self.flash_loan_counter + 1;
+            self.outstanding_flash_loans = self.outstanding_flash_loans + 1;
+
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.flash_loan_receipt_address);
+            let receipt: Bucket = self.tracking_token_admin_badge.authorize(|| {
+                receipt_manager.mint_non_fungible(&receipt_id, receipt_data)
+            });
+
+            return (tokens, receipt);
+        }
+
+        /// Repays a flash loan taken out from this liquidity pool.
+        /// 
+        /// This method performs a number of checks before the loan is repaid:
+        /// 
+        /// * **Check 1:** Checks that the receipt is a single flash loan receipt of this liquidity pool.
+        /// * **Check 2:** Checks that the repayment is of the tokens which were borrowed.
+        /// * **Check 3:** Checks that the repayment covers the amount borrowed and the fee.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `repayment` (Bucket) - A bucket of the tokens to repay the loan with.
+        /// * `receipt` (Bucket) - A bucket of the transient receipt of the flash loan.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the repayment.
+        pub fn repay_flash_loan(
+            &mut self,
+            mut repayment: Bucket,
+            receipt: Bucket
+        ) -> Bucket {
+            assert_eq!(
+                receipt.resource_address(), self.flash_loan_receipt_address,
+                "[Repay Flash Loan]: The receipt given does not belong to this liquidity pool."
+            );
+            assert_eq!(receipt.amount(), Decimal::one(), "[Repay Flash Loan]: Exactly one receipt must be given.");
+
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.flash_loan_receipt_address);
+            let receipt_data: FlashLoanReceipt = receipt_manager.get_non_fungible_data(&receipt.non_fungible_local_id());
+            assert_eq!(
+                repayment.resource_address(), receipt_data.resource_address,
+                "[Repay Flash Loan]: The loan must be repaid with the tokens which were borrowed."
+            );
+            assert!(
+                repayment.amount() >= receipt_data.amount + receipt_data.fee,
+                "[Repay Flash Loan]: The repayment must cover the amount borrowed and the fee of {}.",
+                receipt_data.fee
+            );
+            info!("[Repay Flash Loan]: Repaying {} with a fee of {}", receipt_data.amount, receipt_data.fee);
+
+            // Depositing the repayment and the fee into the pool and burning the receipt
+            self.deposit(repayment.take(receipt_data.amount + receipt_data.fee));
+            self.tracking_token_admin_badge.authorize(|| {
+                receipt.burn();
+            });
+            self.outstanding_flash_loans = self.outstanding_flash_loans - 1;
+            self.update_oracle();
+
+            return repayment;
+        }
+
+        /// Gets the resource address of the transient receipts of the flash swaps of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `ResourceAddress` - The resource address of the flash swap receipts.
+        pub fn flash_swap_receipt_address(&self) -> ResourceAddress {
+            return self.flash_swap_receipt_address;
+        }
+
+        /// Gives out tokens from this liquidity pool before they're paid for.
+        /// 
+        /// A flash swap is a swap where the output is given out first and the input is paid afterwards, which allows 
+        /// the output to be used, for example in an arbitrage, to get the tokens needed to pay for it. Along with the
+        /// output tokens, a transient receipt is returned which can not be deposited. The flash swap must be settled 
+        /// through `settle_flash_swap` before the end of the transaction, otherwise the whole transaction fails.
+        /// 
+        /// This method performs a number of checks before the tokens are given out:
+        /// 
+        /// * **Check 1:** Checks that the liquidity pool is not paused or in another flash loan or flash swap.
+        /// * **Check 2:** Checks that the resource address given does indeed belong to this liquidity pool.
+        /// * **Check 3:** Checks that the amount is positive and less than the reserves of the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the tokens to receive.
+        /// * `amount_out` (Decimal) - The amount of tokens to receive.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        /// * `Bucket` - A bucket of the transient receipt of the flash swap.
+        pub fn flash_swap(
+            &mut self,
+            output_resource_address: ResourceAddress,
+            amount_out: Decimal
+        ) -> (Bucket, Bucket) {
+            self.assert_not_paused(String::from("Flash Swap"));
+            self.assert_not_halted(String::from("Flash Swap"));
+            self.assert_no_flash_loan(String::from("Flash Swap"));
+            self.assert_belongs_to_pool(output_resource_address, String::from("Flash Swap"));
+            assert!(
+                (amount_out > Decimal::zero()) & (amount_out < self.vaults[&output_resource_address].amount()),
+                "[Flash Swap]: The amount out must be positive and less than the reserves of the pool."
+            );
+
+            let receipt_data: FlashSwapReceipt = FlashSwapReceipt {
+                output_resource_address: output_resource_address,
+                amount_out: amount_out,
+                reserves: (
+                    self.vaults[&self.other_resource_address(output_resource_address)].amount(),
+                    self.vaults[&output_resource_address].amount()
+                ),
+                price_before: self.vault_price()
+            };
+            info!("[Flash Swap]: Giving out {} of {:?} before payment", amount_out, output_resource_address);
+
+            // Taking the tokens out of the pool and minting the receipt of the flash swap
+            let output_tokens: Bucket = self.withdraw(output_resource_address, amount_out);
+            let receipt_id: NonFungibleLocalId = NonFungibleLocalId::integer(self.flash_swap_counter);
+            self.flash_swap_counter = self.flash_swap_counter + 1;
+            self.outstanding_flash_loans = self.outstanding_flash_loans + 1;
+
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.flash_swap_receipt_address);
+            let receipt: Bucket = self.tracking_token_admin_badge.authorize(|| {
+                receipt_manager.mint_non_fungible(&receipt_id, receipt_data)
+            });
+
+            return (output_tokens, receipt);
+        }
+
+        /// Settles a flash swap taken out from this liquidity pool.
+        /// 
+        /// The flash swap may be paid for with either of the two tokens of the pool. The amount required is the amount
+        /// that keeps `k` from decreasing once the fee is taken out of the payment:
+        /// 
+        /// * Paying with the other token, the amount is the input of a regular swap for the output, which is `dx` in
+        /// `(x + rdx)(y - dy) = xy`.
+        /// * Paying with the output token, the amount is `dy / r` so that the output is returned along with the fee.
+        /// 
+        /// This method performs a number of checks before the flash swap is settled:
+        /// 
+        /// * **Check 1:** Checks that the receipt is a single flash swap receipt of this liquidity pool.
+        /// * **Check 2:** Checks that the payment is of one of the two tokens of this liquidity pool.
+        /// * **Check 3:** Checks that the payment covers the amount required.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `payment` (Bucket) - A bucket of the tokens to pay for the flash swap with.
+        /// * `receipt` (Bucket) - A bucket of the transient receipt of the flash swap.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the payment.
+        pub fn settle_flash_swap(
+            &mut self,
+            mut payment: Bucket,
+            receipt: Bucket
+        ) -> Bucket {
+            assert_eq!(
+                receipt.resource_address(), self.flash_swap_receipt_address,
+                "[Settle Flash Swap]: The receipt given does not belong to this liquidity pool."
+            );
+            assert_eq!(receipt.amount(), Decimal::one(), "[Settle Flash Swap]: Exactly one receipt must be given.");
+            self.assert_not_halted(String::from("Settle Flash Swap"));
+            self.assert_belongs_to_pool(payment.resource_address(), String::from("Settle Flash Swap"));
+
+            let receipt_manager: ResourceManager = borrow_resource_manager!(self.flash_swap_receipt_address);
+            let receipt_data: FlashSwapReceipt = receipt_manager.get_non_fungible_data(&receipt.non_fungible_local_id());
+            let r: Decimal = (dec!("100") - self.effective_fee()) / dec!("100");
+            let (x, y): (Decimal, Decimal) = receipt_data.reserves;
+            let dy: Decimal = receipt_data.amount_out;
+            let amount_required: Decimal = if payment.resource_address() == receipt_data.output_resource_address {
+                dy / r
+            } else {
+                (dy * x) / (r * (y - dy))
+            };
+            assert!(
+                payment.amount() >= amount_required,
+                "[Settle Flash Swap]: Not enough payment for the flash swap. Payment required is {}",
+                amount_required
+            );
+            info!("[Settle Flash Swap]: Settling with {} of {:?}", amount_required, payment.resource_address());
+
+            // Depositing the payment, less the protocol's share of the fee, into the pool and burning the receipt
+            let mut payment_tokens: Bucket = payment.take(amount_required);
+            self.take_protocol_fee(&mut payment_tokens);
+            self.deposit(payment_tokens);
+            self.tracking_token_admin_badge.authorize(|| {
+                receipt.burn();
+            });
+            self.outstanding_flash_loans = self.outstanding_flash_loans - 1;
+
+            info!("[Settle Flash Swap]: K after flash swap: {}, K before: {}", self.k(), x * y);
+            self.check_circuit_breaker(receipt_data.price_before, String::from("Settle Flash Swap"));
+            self.record_trade_price();
+            self.update_oracle();
+            return payment;
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,1070 @@
+use scrypto::prelude::*;
+use crate::utils::*;
+
+/// The precision at which the Newton's method iterations of the StableSwap invariant are considered to have converged.
+fn convergence_threshold() -> Decimal {
+    return dec!("0.000000000000000001");
+}
+
+/// The largest number of Newton's method iterations taken by the StableSwap calculations of the pool. The iterations
+/// stop as soon as they have converged, which is usually after a handful of them.
+pub const MAX_NEWTON_ITERATIONS: u32 = 255;
+
+/// The smallest unit of a `Decimal`, used to round the results of the Newton's method iterations in the favour of the 
+/// pool.
+fn smallest_unit() -> Decimal {
+    return dec!("0.000000000000000001");
+}
+
+/// Calculates the StableSwap invariant `D` for the given reserves.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - The reserves of the first token.
+/// * `y` (Decimal) - The reserves of the second token.
+/// * `amplification` (Decimal) - The amplification coefficient `A` of the pool.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The value of `D` which satisfies the invariant for the given reserves.
+/// 
+/// # Note:
+/// 
+/// For two tokens the StableSwap invariant is `4A(x + y) + D = 4AD + D^3 / (4xy)`. There is no closed form solution
+/// for `D`, so it is found through Newton's method starting from `D = x + y`, which is the solution when `A` goes to
+/// infinity.
+/// 
+/// A pool which has been emptied out of either of its tokens has no liquidity to trade against, so its `D` is zero.
+pub fn compute_d(x: Decimal, y: Decimal, amplification: Decimal) -> Decimal {
+    if (x == Decimal::zero()) | (y == Decimal::zero()) {
+        return Decimal::zero();
+    }
+
+    let s: Decimal = x + y;
+
+    let ann: Decimal = amplification * dec!("4");
+    let mut d: Decimal = s;
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let d_p: Decimal = d * d / (x * dec!("2")) * d / (y * dec!("2"));
+        let d_previous: Decimal = d;
+        d = (ann * s + d_p * dec!("2")) * d / ((ann - Decimal::one()) * d + d_p * dec!("3"));
+        if (d - d_previous).abs() <= convergence_threshold() {
+            break;
+        }
+    }
+    return d;
+}
+
+/// Calculates the reserves of one token that keep the StableSwap invariant `D` given the reserves of the other token.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - The reserves of the other token.
+/// * `d` (Decimal) - The StableSwap invariant to keep.
+/// * `amplification` (Decimal) - The amplification coefficient `A` of the pool.
+/// * `max_iterations` (u32) - The largest number of Newton's method iterations to take.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The reserves `y` of the token which satisfy the invariant.
+/// 
+/// # Note:
+/// 
+/// Substituting `x` and `D` into the invariant gives the quadratic `y^2 + (b - D)y = c` where `b = x + D / 4A` and
+/// `c = D^3 / (16Ax)`, which is solved through Newton's method starting from `y = D`.
+/// 
+/// An invariant of zero, or reserves of zero of the other token, leave no reserves to keep, so zero is returned.
+fn compute_y(x: Decimal, d: Decimal, amplification: Decimal, max_iterations: u32) -> Decimal {
+    if (x == Decimal::zero()) | (d == Decimal::zero()) {
+        return Decimal::zero();
+    }
+
+    let ann: Decimal = amplification * dec!("4");
+    let c: Decimal = d * d / (x * dec!("2")) * d / (ann * dec!("2"));
+    let b: Decimal = x + d / ann;
+
+    let mut y: Decimal = d;
+    for _ in 0..max_iterations {
+        let y_previous: Decimal = y;
+        y = (y * y + c) / (y * dec!("2") + b - d);
+        if (y - y_previous).abs() <= convergence_threshold() {
+            break;
+        }
+    }
+    return y;
+}
+
+/// Calculates the amount of output tokens that a swap of the given net amount of input tokens gives through the 
+/// StableSwap invariant.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - The reserves of the input token.
+/// * `y` (Decimal) - The reserves of the output token.
+/// * `d` (Decimal) - The StableSwap invariant of the reserves.
+/// * `net_input_amount` (Decimal) - The amount of input tokens after the fee has been taken out of them.
+/// * `amplification` (Decimal) - The amplification coefficient `A` of the pool.
+/// * `max_iterations` (u32) - The largest number of Newton's method iterations to take, which is 
+/// `MAX_NEWTON_ITERATIONS` for swaps and may be fewer for estimates.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The amount of output tokens `dy` such that `(x + dx, y - dy)` keeps the invariant `D`. As in Curve, 
+/// one unit is taken off of `dy` so that the truncation of the Newton's method iterations is always in the favour of
+/// the pool.
+pub fn compute_output_amount(
+    x: Decimal,
+    y: Decimal,
+    d: Decimal,
+    net_input_amount: Decimal,
+    amplification: Decimal,
+    max_iterations: u32
+) -> Decimal {
+    if d == Decimal::zero() {
+        return Decimal::zero();
+    }
+    let dy: Decimal = y - compute_y(x + net_input_amount, d, amplification, max_iterations) - smallest_unit();
+    return if dy > Decimal::zero() { dy } else { Decimal::zero() };
+}
+
+/// Calculates the spot price of the first token in terms of the second token at the given reserves.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - The reserves of the token to get the price of.
+/// * `y` (Decimal) - The reserves of the token the price is given in.
+/// * `d` (Decimal) - The StableSwap invariant of the reserves.
+/// * `amplification` (Decimal) - The amplification coefficient `A` of the pool.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The slope of the invariant curve at the reserves, or zero if either of the reserves is empty.
+/// 
+/// # Note:
+/// 
+/// The slope is found by implicit differentiation of the invariant: 
+/// `-dy/dx = (4A + D^3 / (4x^2y)) / (4A + D^3 / (4xy^2))`.
+pub fn compute_spot_price(
+    x: Decimal,
+    y: Decimal,
+    d: Decimal,
+    amplification: Decimal
+) -> Decimal {
+    if (x == Decimal::zero()) | (y == Decimal::zero()) {
+        return Decimal::zero();
+    }
+    let ann: Decimal = amplification * dec!("4");
+    let d_cubed: Decimal = d * d * d;
+    return (ann + d_cubed / (dec!("4") * x * x * y)) / (ann + d_cubed / (dec!("4") * x * y * y));
+}
+
+#[blueprint]
+mod stable_pool {
+
+    /// StablePool encapsulate liquidity pool fields and methods for pegged assets
+    /// Uses the StableSwap invariant which behaves like the constant sum `x + y = D` close to the peg and like the
+    /// constant product `x * y = k` away from it, giving far better execution between tokens that trade close to 1:1
+    pub struct StablePool{
+       /// use a more flexible dynamic way to store the vault addresses.
+       /// Note: StablePool object always have exactly 2 vaults
+       vaults: HashMap<ResourceAddress, Vault>,
+
+       /// Tracking token is used keep track of the ratio user's contribution
+       /// proportional to the total pool amount. This ratio will be used to calculate
+       /// fees distributed and also for when user withdraws their liquidity out of the pool
+       tracking_token_address: ResourceAddress,
+
+       /// Admin badge used to mint and burn tracking token for this pool
+       tracking_token_admin_badge: Vault,
+
+       /// Decimal Amount between 0 to 100 representing the percentage fee
+       /// paid to liquidity pool (to be distributed to the liquidity providers
+       /// based on thier LP tracking token ratio )
+       fee_to_pool: Decimal,
+
+       /// The amplification coefficient `A` of the StableSwap invariant. The higher it is, the longer the
+       /// pool behaves like the constant sum around the peg.
+       amplification: Decimal,
+
+       /// Decimal Amount between 0 to 100 representing the percentage of the swap 
+       /// fee which is diverted to the protocol instead of the liquidity providers
+       protocol_fee: Decimal,
+
+       /// Vaults holding the protocol fees collected from swaps until they're collected
+       protocol_vaults: HashMap<ResourceAddress, Vault>,
+
+       /// When the pool is paused no swaps can be made and no liquidity can be added,
+       /// but liquidity providers are still able to remove their liquidity
+       paused: bool
+    }
+
+    impl StablePool {
+        /// Creates a new stable pool based on two resources addresses, fee amount to go to the pool and amplification
+        /// validations include:
+        ///  - Check the two resource addresses are not the same
+        ///  - Check resources are both fungible
+        ///  - Check the input token buckets are not empty
+        ///  - Check fee amount set is decimal between 0 to 100, excluding 100
+        ///  - Check amplification is between 1 and 10000
+        /// Returns LP Tracking Token (for the initial liquidity provider
+        /// Note: no change amount is returned as pool ratio is not established yet
+        pub fn instantiate_pool(
+            token1: Bucket,
+            token2: Bucket,
+            fee_to_pool: Decimal,
+            amplification: Decimal) -> (StablePoolComponent, Bucket) {
+            // Check token addresses are not the same
+            assert_ne!(
+                token1.resource_address(), token2.resource_address(),
+                "[Stable Pool Creation]: Liquidity pools may only be created between two different tokens."
+            );
+
+            // Check resources neither is Non-Fungible
+            assert_eq!(
+                borrow_resource_manager!(token1.resource_address()).resource_type().is_fungible(), true,
+                "[Stable Pool Creation]: Both assets must be fungible."
+            );
+            assert_eq!(
+                borrow_resource_manager!(token2.resource_address()).resource_type().is_fungible(), true,
+                "[Stable Pool Creation]: Both assets must be fungible."
+            );
+
+            // Check the input token buckets are not empty
+            assert!(
+                !token1.is_empty() & !token2.is_empty(),
+                "[Stable Pool Creation]: Can't create a pool from an empty bucket."
+            );
+
+            // Check fee amount set is decimal between 0 to 100, excluding 100 since no input would be left to swap with
+            assert!(
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")),
+                "[Stable Pool Creation]: Fee must be between 0 and 100, excluding 100"
+            );
+
+            // Check amplification is between 1 and 10000
+            assert!(
+                (amplification >= Decimal::one()) & (amplification <= dec!("10000")),
+                "[Stable Pool Creation]: Amplification must be between 1 and 10000"
+            );
+
+            // Validation is done
+            info!(
+                "[instantiate_pool]: validation of inputs done. Inputs: token1 {:?}: {}, token2 {:?}: {}, fee_to_pool: {}, amplification: {}",
+                token1.resource_address(), token1.amount(), token2.resource_address(), token2.amount(), fee_to_pool, amplification
+            );
+
+            // Sort and build Hashmap of the two resource token addresses
+            let (bucket1, bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let addresses: (ResourceAddress, ResourceAddress) = (bucket1.resource_address(), bucket2.resource_address());
+
+            let lp_id: String = format!("{:?}-{:?}", addresses.0, addresses.1);
+            let pair_name: String = address_pair_symbol(addresses.0, addresses.1);
+
+            info!(
+                "[Stable Pool Creation]: Creating new pool between tokens: {}, of name: {}, Ratio: {}:{}",
+                lp_id, pair_name, bucket1.amount(), bucket2.amount()
+            );
+
+            let mut vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            let mut protocol_vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            protocol_vaults.insert(bucket1.resource_address(), Vault::new(bucket1.resource_address()));
+            protocol_vaults.insert(bucket2.resource_address(), Vault::new(bucket2.resource_address()));
+            vaults.insert(bucket1.resource_address(), Vault::with_bucket(bucket1));
+            vaults.insert(bucket2.resource_address(), Vault::with_bucket(bucket2));
+
+            // Create Admin badge to give authority for minting and burning LP tracking tokens
+            let tracking_token_admin_badge: Bucket = ResourceBuilder::new_fungible()
+            .divisibility(DIVISIBILITY_NONE)
+            .metadata("name", "Tracking Token Admin Badge")
+            .metadata("symbol", "TTAB")
+            .metadata("description", "This is an admin badge that has the authority to mint and burn tracking tokens")
+            .metadata("lp_id", format!("{}", lp_id))
+            .mint_initial_supply(1);
+
+            // Creating the tracking tokens and minting the amount owed to the initial liquidity provider
+            let tracking_tokens: Bucket = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .metadata("name", format!("{} Stable LP Tracking Token", pair_name))
+                .metadata("symbol", "TT")
+                .metadata("description", "A tracking token used to track the percentage ownership of liquidity providers over the stable liquidity pool")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .mint_initial_supply(100);
+
+            // Creating the liquidity pool component and instantiating it
+            let liquidity_pool = Self {
+                vaults: vaults,
+                tracking_token_address: tracking_tokens.resource_address(),
+                tracking_token_admin_badge: Vault::with_bucket(tracking_token_admin_badge),
+                fee_to_pool: fee_to_pool,
+                amplification: amplification,
+                protocol_fee: Decimal::zero(),
+                protocol_vaults: protocol_vaults,
+                paused: false
+            }
+            .instantiate()
+            // .globalize() NOTE: comment out if running manifests under `./manifests/pool` and using setup_pool_test.sh
+            ;
+
+            return (liquidity_pool, tracking_tokens);
+        }
+
+        /// Checks if the given address belongs to this pool or not.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `bool` - A boolean of whether the address belongs to this pool or not.
+        pub fn belongs_to_pool(
+            &self,
+            address: ResourceAddress
+        ) -> bool {
+            return self.vaults.contains_key(&address);
+        }
+
+        /// Asserts that the given address belongs to the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        pub fn assert_belongs_to_pool(
+            &self,
+            address: ResourceAddress,
+            label: String
+        ) {
+            assert!(
+                self.belongs_to_pool(address),
+                "[{}]: The provided resource address does not belong to the pool.",
+                label
+            );
+        }
+
+        /// Gets the resource addresses of the tokens in this liquidity pool and returns them as a `Vec<ResourceAddress>`.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Vec<ResourceAddress>` - A vector of the resource addresses of the tokens in this liquidity pool.
+        pub fn addresses(&self) -> Vec<ResourceAddress> {
+            return self.vaults.keys().cloned().collect::<Vec<ResourceAddress>>();
+        }
+
+        /// Gets the name of the given liquidity pool from the symbols of the two tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// `String` - A string of the pair symbol
+        pub fn name(&self) -> String {
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return address_pair_symbol(addresses[0], addresses[1]);
+        }
+
+        /// Gets the resource address of the tracking tokens of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the tracking tokens.
+        pub fn tracking_token_address(&self) -> ResourceAddress {
+            return self.tracking_token_address;
+        }
+
+        /// Gets the percentage fee paid to this liquidity pool on swaps.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the fee paid to the pool.
+        pub fn fee_to_pool(&self) -> Decimal {
+            return self.fee_to_pool;
+        }
+
+        /// Sets the percentage fee paid to this stable pool on swaps.
+        /// 
+        /// This method performs a number of checks before the fee is set:
+        /// 
+        /// * **Check 1:** Checks that the fee is a decimal between 0 and 100, excluding 100 since no input would be 
+        /// left to swap with.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `fee_to_pool` (Decimal) - The new percentage fee paid to the pool on swaps.
+        pub fn set_fee_to_pool(
+            &mut self,
+            fee_to_pool: Decimal
+        ) {
+            assert!(
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")), 
+                "[Stable Set Fee]: Fee must be between 0 and 100, excluding 100"
+            );
+            info!("[Stable Set Fee]: Fee set from {} to {}", self.fee_to_pool, fee_to_pool);
+
+            self.fee_to_pool = fee_to_pool;
+        }
+
+        /// Gets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the share of the swap fee paid to the protocol.
+        pub fn protocol_fee(&self) -> Decimal {
+            return self.protocol_fee;
+        }
+
+        /// Sets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// This method performs a number of checks before the protocol fee is set:
+        /// 
+        /// * **Check 1:** Checks that the protocol fee is a decimal between 0 and 100.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `protocol_fee` (Decimal) - The percentage of the swap fee to divert to the protocol.
+        pub fn set_protocol_fee(
+            &mut self,
+            protocol_fee: Decimal
+        ) {
+            assert!(
+                (protocol_fee >= Decimal::zero()) & (protocol_fee <= dec!("100")), 
+                "[Stable Set Protocol Fee]: Protocol fee must be between 0 and 100"
+            );
+            info!("[Stable Set Protocol Fee]: Protocol fee set from {} to {}", self.protocol_fee, protocol_fee);
+
+            self.protocol_fee = protocol_fee;
+        }
+
+        /// Withdraws all of the protocol fees collected by this stable pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the protocol fees of each of the two tokens.
+        pub fn collect_protocol_fees(&mut self) -> Vec<Bucket> {
+            return self.protocol_vaults
+                .values_mut()
+                .map(|vault| vault.take_all())
+                .collect::<Vec<Bucket>>();
+        }
+
+        /// Takes the share of the fee of a swap which goes to the protocol out of the input tokens and puts it into the
+        /// protocol vaults. The output of the swap is not affected by this, only less of the fee is deposited into the
+        /// pool for the liquidity providers.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (&mut Bucket) - A bucket of the input tokens of a swap.
+        fn take_protocol_fee(
+            &mut self,
+            tokens: &mut Bucket
+        ) {
+            let protocol_amount: Decimal = tokens.amount() * self.fee_to_pool / dec!("100") 
+                * self.protocol_fee / dec!("100");
+            if protocol_amount > Decimal::zero() {
+                info!(
+                    "[Stable Protocol Fee]: Taking {} of {:?} as protocol fee", 
+                    protocol_amount, tokens.resource_address()
+                );
+                self.protocol_vaults.get_mut(&tokens.resource_address()).unwrap().put(tokens.take(protocol_amount));
+            }
+        }
+
+        /// Gets whether this stable pool is paused or not.
+        /// 
+        /// # Returns:
+        /// 
+        /// `bool` - A boolean of whether the pool is paused.
+        pub fn paused(&self) -> bool {
+            return self.paused;
+        }
+
+        /// Pauses or unpauses this stable pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `paused` (bool) - Whether the pool should be paused.
+        pub fn set_paused(
+            &mut self,
+            paused: bool
+        ) {
+            info!("[Stable Set Paused]: Pool paused set from {} to {}", self.paused, paused);
+            self.paused = paused;
+        }
+
+        /// Asserts that this stable pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_paused(
+            &self,
+            label: String
+        ) {
+            assert!(!self.paused, "[{}]: The stable pool is paused.", label);
+        }
+
+        /// Gets the amplification coefficient of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The amplification coefficient `A` of the StableSwap invariant.
+        pub fn amplification(&self) -> Decimal {
+            return self.amplification;
+        }
+
+        /// Gets the amount of reserves of the given token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the reserves of.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The amount of the given token in the vaults of this liquidity pool.
+        pub fn reserve(
+            &self,
+            resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(resource_address, String::from("Stable Reserve"));
+
+            return self.vaults[&resource_address].amount();
+        }
+
+        /// This method takes in a resource address and if this resource address belongs to the pool it returns the
+        /// address of the other token in this liquidity pool.
+        /// 
+        /// # Arguments
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address for a token from the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `ResourceAddress` - The address of the other token in this pool.
+        pub fn other_resource_address(
+            &self,
+            resource_address: ResourceAddress
+        ) -> ResourceAddress {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(resource_address, String::from("Argument Resource Address"));
+
+            // Checking which of the addresses was provided as an argument and returning the other address.
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return if addresses[0] == resource_address {addresses[1]} else {addresses[0]};
+        }
+
+        /// Gets the sorted resource addresses of the two tokens in this stable pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `(ResourceAddress, ResourceAddress)` - The resource addresses of the two tokens sorted by `sort_addresses`.
+        fn sorted_addresses(&self) -> (ResourceAddress, ResourceAddress) {
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return sort_addresses(addresses[0], addresses[1]);
+        }
+
+        /// Calculates the StableSwap invariant `D` at the current reserves.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The value of `D`, which is the total amount of both tokens when they are balanced.
+        pub fn d(&self) -> Decimal {
+            let addresses: Vec<ResourceAddress> = self.addresses();
+            return compute_d(
+                self.vaults[&addresses[0]].amount(),
+                self.vaults[&addresses[1]].amount(),
+                self.amplification
+            );
+        }
+
+        /// Calculates the spot price of the given token in terms of the other token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the token to get the price of.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of the other token that one input token is worth at the current reserves, not
+        /// taking the fee or the price impact into account.
+        /// 
+        /// # Note:
+        /// 
+        /// The spot price is the slope of the invariant curve at the current reserves, which is found by implicit
+        /// differentiation of the invariant: `-dy/dx = (4A + D^3 / (4x^2y)) / (4A + D^3 / (4xy^2))`.
+        pub fn spot_price(
+            &self,
+            input_resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(input_resource_address, String::from("Stable Spot Price"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&self.other_resource_address(input_resource_address)].amount();
+            return compute_spot_price(x, y, self.d(), self.amplification);
+        }
+
+        /// This method calculates the amount of output tokens that would be received for a given amount of an input
+        /// token. This is calculated through the StableSwap invariant.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that the provided resource address belongs to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to calculate the output for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The output amount for the given input.
+        /// 
+        /// # Note:
+        /// 
+        /// This method finds `dy` such that the reserves `(x + rdx, y - dy)` have the same invariant `D` as the current
+        /// reserves `(x, y)`, where `r = (100 - fee) / 100` is the fee modifier. As in Curve, one unit is taken off of 
+        /// `dy` so that the truncation of the Newton's method iterations is always in the favour of the pool.
+        pub fn calculate_output_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            input_amount: Decimal
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(input_resource_address, String::from("Stable Calculate Output"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&self.other_resource_address(input_resource_address)].amount();
+            let dx: Decimal = input_amount;
+            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+
+            let d: Decimal = compute_d(x, y, self.amplification);
+            return compute_output_amount(x, y, d, r * dx, self.amplification, MAX_NEWTON_ITERATIONS);
+        }
+
+        /// This method calculates the amount of input tokens that would be required to receive the specified amount of
+        /// output tokens. This is calculated through the StableSwap invariant.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that the provided resource address belongs to this liquidity pool.
+        /// * **Check 2:** Checks that the output amount is less than the reserves of the output token.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `output_amount` (Decimal) - The amount of output tokens to calculate the input for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The input amount for the given output.
+        /// 
+        /// # Note:
+        /// 
+        /// This method finds `dx` such that the reserves `(x + rdx, y - dy)` have the same invariant `D` as the current
+        /// reserves `(x, y)`, where `r = (100 - fee) / 100` is the fee modifier. One unit is added to the new reserves
+        /// of `x` for the same reason that one is taken off of the output in `calculate_output_amount`.
+        pub fn calculate_input_amount(
+            &self,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(output_resource_address, String::from("Stable Calculate Input"));
+
+            let x: Decimal = self.vaults[&self.other_resource_address(output_resource_address)].amount();
+            let y: Decimal = self.vaults[&output_resource_address].amount();
+            let dy: Decimal = output_amount;
+            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+            assert!(dy < y, "[Stable Calculate Input]: Not enough liquidity for the desired amount of output.");
+
+            // The invariant is symmetric in `x` and `y`, so `compute_y` can be used to find the new reserves of `x`.
+            let d: Decimal = compute_d(x, y, self.amplification);
+            let x_after_swap: Decimal = compute_y(y - dy, d, self.amplification, MAX_NEWTON_ITERATIONS);
+            let dx: Decimal = (x_after_swap - x + smallest_unit()) / r;
+            return dx;
+        }
+
+        /// Deposits a bucket of tokens into this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `bucket` (Bucket) - A buckets of the tokens to deposit into the liquidity pool
+        fn deposit(
+            &mut self,
+            bucket: Bucket
+        ) {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(bucket.resource_address(), String::from("Stable Deposit"));
+
+            self.vaults.get_mut(&bucket.resource_address()).unwrap().put(bucket);
+        }
+
+        /// Withdraws tokens from the liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The address of the resource to withdraw from the liquidity pool.
+        /// * `amount` (Decimal) - The amount of tokens to withdraw from the liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the withdrawn tokens.
+        fn withdraw(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal
+        ) -> Bucket {
+            // Performing the checks to ensure tha the withdraw can actually go through
+            self.assert_belongs_to_pool(resource_address, String::from("Stable Withdraw"));
+
+            // Getting the vault of that resource and checking if there is enough liquidity to perform the withdraw.
+            let vault: &mut Vault = self.vaults.get_mut(&resource_address).unwrap();
+            assert!(
+                vault.amount() >= amount,
+                "[Stable Withdraw]: Not enough liquidity available for the withdraw."
+            );
+
+            return vault.take(amount);
+        }
+
+        /// Adds liquidity to this liquidity pool in exchange for liquidity provider tracking tokens.
+        /// 
+        /// Liquidity is added in the ratio of the current reserves in the same way as the constant product `Pool`,
+        /// where any excess of one of the tokens is returned to the liquidity provider. This keeps the share of every
+        /// liquidity provider proportional to the reserves regardless of how far the pool is from the peg.
+        /// 
+        /// This method performs a number of checks before liquidity is added to the pool:
+        /// 
+        /// * **Check 1:** Checks that the stable pool is not paused.
+        /// * **Check 2:** Checks that the buckets passed are of tokens that belong to this liquidity pool.
+        /// * **Check 3:** Checks that the buckets passed are not empty.
+        /// * **Check 4:** Checks that the amounts deposited and the tracking tokens minted are not below the minimums.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `token1` (Bucket) - A bucket containing the amount of the first token to add to the pool.
+        /// * `token2` (Bucket) - A bucket containing the amount of the second token to add to the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_token1_deposit` (Decimal) - The minimum amount of the `token1` tokens that must be deposited.
+        /// * `min_token2_deposit` (Decimal) - The minimum amount of the `token2` tokens that must be deposited.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the remaining tokens of the `token1` type.
+        /// * `Bucket` - A bucket of the remaining tokens of the `token2` type.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        pub fn add_liquidity(
+            &mut self,
+            token1: Bucket,
+            token2: Bucket,
+            min_tracking_tokens_out: Decimal,
+            min_token1_deposit: Decimal,
+            min_token2_deposit: Decimal
+        ) -> (Bucket, Bucket, Bucket) {
+            // Checking that the pool is not paused and that the tokens belong to this liquidity pool.
+            self.assert_not_paused(String::from("Stable Add Liquidity"));
+            self.assert_belongs_to_pool(token1.resource_address(), String::from("Stable Add Liquidity"));
+            self.assert_belongs_to_pool(token2.resource_address(), String::from("Stable Add Liquidity"));
+
+            // Checking that the buckets passed are not empty
+            assert!(!token1.is_empty(), "[Stable Add Liquidity]: Can not add liquidity from an empty bucket");
+            assert!(!token2.is_empty(), "[Stable Add Liquidity]: Can not add liquidity from an empty bucket");
+
+            // Sorting out the two buckets passed and getting the values of `dm` and `dn`. The minimum deposits are 
+            // sorted along with the buckets.
+            let sorted: bool = token1.resource_address() == self.sorted_addresses().0;
+            let (min_amount1, min_amount2): (Decimal, Decimal) = if sorted {
+                (min_token1_deposit, min_token2_deposit)
+            } else {
+                (min_token2_deposit, min_token1_deposit)
+            };
+            let (mut bucket1, mut bucket2): (Bucket, Bucket) = sort_buckets(token1, token2);
+            let dm: Decimal = bucket1.amount();
+            let dn: Decimal = bucket2.amount();
+
+            // Getting the values of m and n from the liquidity pool vaults (What is already in the pool)
+            let m: Decimal = self.vaults[&bucket1.resource_address()].amount();
+            let n: Decimal = self.vaults[&bucket2.resource_address()].amount();
+
+            // Computing the amount of tokens to deposit into the liquidity pool from each one of the buckets passed
+            let (amount1, amount2): (Decimal, Decimal) = if ((m == Decimal::zero()) | (n == Decimal::zero())) | ((m * dn) == (n * dm)) {
+                (dm, dn)
+            } else if (m / n) < (dm / dn) {
+                (dn * m / n, dn)
+            } else {
+                (dm, dm * n / m)
+            };
+            info!(
+                "[Stable Add Liquidity]: Liquidity amount to add: {:?}: {}, {:?}: {}",
+                bucket1.resource_address(), amount1, bucket2.resource_address(), amount2
+            );
+            assert!(
+                (amount1 >= min_amount1) & (amount2 >= min_amount2),
+                "[Stable Add Liquidity]: The amounts deposited are below the minimums. The reserve ratio may have moved."
+            );
+
+            // Depositing the amount of tokens calculated into the liquidity pool
+            self.deposit(bucket1.take(amount1));
+            self.deposit(bucket2.take(amount2));
+
+            // Computing the amount of tracking tokens that the liquidity provider is owed and minting them.
+            let tracking_tokens_manager: ResourceManager = borrow_resource_manager!(self.tracking_token_address);
+            let tracking_amount: Decimal = if tracking_tokens_manager.total_supply() == Decimal::zero() {
+                dec!("100.00")
+            } else {
+                amount1 * tracking_tokens_manager.total_supply() / m
+            };
+            let tracking_tokens: Bucket = self.tracking_token_admin_badge.authorize(|| {
+                tracking_tokens_manager.mint(tracking_amount)
+            });
+            info!("[Stable Add Liquidity]: Owed amount of tracking tokens: {}", tracking_amount);
+            assert!(
+                tracking_amount >= min_tracking_tokens_out,
+                "[Stable Add Liquidity]: Tracking tokens minted ({}) are below the minimum of {}.",
+                tracking_amount, min_tracking_tokens_out
+            );
+
+            // Returning the remaining tokens from `token1`, `token2`, and the tracking tokens
+            return (bucket1, bucket2, tracking_tokens);
+        }
+
+        /// Removes the percentage of the liquidity owed to this liquidity provider.
+        /// 
+        /// This method performs a number of checks before liquidity removed from the pool:
+        /// 
+        /// * **Check 1:** Checks to ensure that the tracking tokens passed do indeed belong to this liquidity pool.
+        /// * **Check 2:** Checks that the amounts withdrawn are not below the minimums.
+        /// 
+        /// The minimums follow the order of the two tokens as sorted by `sort_addresses`, which is also the order that
+        /// the two buckets are returned in.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to
+        /// exchange for their share of the liquidity.
+        /// * `min_amount1` (Decimal) - The minimum amount of the first sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// * `min_amount2` (Decimal) - The minimum amount of the second sorted token that the liquidity provider is 
+        /// willing to accept before the method fails.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the first sorted token.
+        /// * `Bucket` - A Bucket of the share of the liquidity provider of the second sorted token.
+        pub fn remove_liquidity(
+            &mut self,
+            tracking_tokens: Bucket,
+            min_amount1: Decimal,
+            min_amount2: Decimal
+        ) -> (Bucket, Bucket) {
+            // Checking the resource address of the tracking tokens passed to ensure that they do indeed belong to this
+            // liquidity pool.
+            assert_eq!(
+                tracking_tokens.resource_address(), self.tracking_token_address,
+                "[Stable Remove Liquidity]: The tracking tokens given do not belong to this liquidity pool."
+            );
+
+            // Calculating the percentage ownership that the tracking tokens amount corresponds to
+            let tracking_tokens_manager: ResourceManager = borrow_resource_manager!(self.tracking_token_address);
+            let percentage: Decimal = tracking_tokens.amount() / tracking_tokens_manager.total_supply();
+
+            info!("User about to withdraw {} of the liquidity", percentage);
+
+            // Burning the tracking tokens
+            self.tracking_token_admin_badge.authorize(|| {
+                tracking_tokens.burn();
+            });
+
+            // Withdrawing the amount of tokens owed to this liquidity provider
+            let addresses: (ResourceAddress, ResourceAddress) = self.sorted_addresses();
+            let bucket1: Bucket = self.withdraw(addresses.0, self.vaults[&addresses.0].amount() * percentage);
+            let bucket2: Bucket = self.withdraw(addresses.1, self.vaults[&addresses.1].amount() * percentage);
+            assert!(
+                (bucket1.amount() >= min_amount1) & (bucket2.amount() >= min_amount2),
+                "[Stable Remove Liquidity]: The amounts withdrawn ({}, {}) are below the minimums. The reserves may \
+                have moved.",
+                bucket1.amount(), bucket2.amount()
+            );
+
+            return (bucket1, bucket2);
+        }
+
+        /// Performs the swap of tokens and takes the pool fee in the process
+        /// 
+        /// This is a low level method that does not perform checks on slippage. It is up to the caller of this method
+        /// to perform the checks needed. The share of the fee set by `protocol_fee` is diverted into the protocol 
+        /// vaults.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the stable pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap(
+            &mut self,
+            mut tokens: Bucket
+        ) -> Bucket {
+            // Checking that the pool is not paused and that the tokens belong to this liquidity pool.
+            self.assert_not_paused(String::from("Stable Swap"));
+            self.assert_belongs_to_pool(tokens.resource_address(), String::from("Stable Swap"));
+            info!("[Stable Swap]: D before swap: {}", self.d());
+
+            // Calculating the output amount for the given input amount of tokens and withdrawing it from the vault
+            let output_amount: Decimal = self.calculate_output_amount(tokens.resource_address(), tokens.amount());
+            info!("[Stable Swap]: output amount is : {}", output_amount);
+            let output_tokens: Bucket = self.withdraw(
+                self.other_resource_address(tokens.resource_address()),
+                output_amount
+            );
+
+            // Depositing the tokens, less the protocol's share of the fee, into the liquidity pool and returning a 
+            // bucket of the swapped tokens.
+            self.take_protocol_fee(&mut tokens);
+            self.deposit(tokens);
+            info!("[Stable Swap]: D after swap: {}", self.d());
+            return output_tokens;
+        }
+
+        /// Swaps all of the given tokens for the other token.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the stable pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `min_amount_out` (Decimal) - The minimum amount of tokens that the caller is willing to accept before the
+        /// method fails.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        pub fn swap_exact_tokens_for_tokens(
+            &mut self,
+            tokens: Bucket,
+            min_amount_out: Decimal
+        ) -> Bucket {
+            // Checking that the pool is not paused and that the bucket passed does indeed belong to this liquidity pool
+            self.assert_not_paused(String::from("Stable Swap Exact"));
+            self.assert_belongs_to_pool(tokens.resource_address(), String::from("Stable Swap Exact"));
+
+            let output_tokens: Bucket = self.swap(tokens);
+            assert!(output_tokens.amount() >= min_amount_out, "[Stable Swap Exact]: min_amount_out not satisfied.");
+
+            return output_tokens;
+        }
+
+        /// Swaps tokens for a specific amount of tokens
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the stable pool is not paused.
+        /// * **Check 2:** Checks that the tokens in the bucket do indeed belong to this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the tokens that the user wishes to swap.
+        /// * `output_amount` (Decimal) - A decimal of the specific amount of output that the user wishes to receive
+        /// from this swap.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the other tokens.
+        /// * `Bucket` - A bucket of the remaining input tokens.
+        pub fn swap_tokens_for_exact_tokens(
+            &mut self,
+            mut tokens: Bucket,
+            output_amount: Decimal
+        ) -> (Bucket, Bucket) {
+            // Checking that the pool is not paused and that the bucket passed does indeed belong to this liquidity pool
+            self.assert_not_paused(String::from("Stable Swap For Exact"));
+            self.assert_belongs_to_pool(tokens.resource_address(), String::from("Stable Swap For Exact"));
+
+            // Calculating the amount of input tokens that would be required to produce the desired amount of output
+            // tokens
+            let input_required: Decimal = self.calculate_input_amount(
+                self.other_resource_address(tokens.resource_address()),
+                output_amount
+            );
+            assert!(
+                tokens.amount() >= input_required,
+                "[Stable Swap For Exact]: Not enough input for the desired amount of output. Input required is {}",
+                input_required
+            );
+
+            // Depositing the amount of input required into the vaults and taking out the requested amount
+            let mut input_tokens: Bucket = tokens.take(input_required);
+            self.take_protocol_fee(&mut input_tokens);
+            self.deposit(input_tokens);
+            let output_tokens: Bucket = self.withdraw(
+                self.other_resource_address(tokens.resource_address()),
+                output_amount
+            );
+            info!("[Stable Swap For Exact]: D after swap: {}", self.d());
+            return (output_tokens, tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_d_of_balanced_reserves_is_their_sum() {
+        for amplification in [dec!("1"), dec!("100"), dec!("5000")] {
+            let d: Decimal = compute_d(dec!("1000"), dec!("1000"), amplification);
+            assert!((d - dec!("2000")).abs() <= dec!("0.000000001"), "D of {} is not 2000", d);
+        }
+    }
+
+    #[test]
+    fn compute_d_is_symmetric() {
+        for (x, y) in [(dec!("1000"), dec!("1")), (dec!("123.45"), dec!("6789")), (dec!("500000"), dec!("499000"))] {
+            let difference: Decimal = (compute_d(x, y, dec!("100")) - compute_d(y, x, dec!("100"))).abs();
+            assert!(difference <= dec!("0.000000001"), "D differs by {} when the reserves are swapped", difference);
+        }
+    }
+
+    #[test]
+    fn compute_d_is_zero_for_empty_reserves() {
+        assert_eq!(compute_d(Decimal::zero(), dec!("1000"), dec!("100")), Decimal::zero());
+        assert_eq!(compute_d(dec!("1000"), Decimal::zero(), dec!("100")), Decimal::zero());
+    }
+
+    #[test]
+    fn compute_d_is_invariant_across_a_swap() {
+        let amplification: Decimal = dec!("100");
+        for (x, y, dx) in [
+            (dec!("1000"), dec!("1000"), dec!("10")), 
+            (dec!("1000"), dec!("1000"), dec!("900")), 
+            (dec!("250000"), dec!("750000"), dec!("1234.5"))
+        ] {
+            let d: Decimal = compute_d(x, y, amplification);
+            let dy: Decimal = compute_output_amount(x, y, d, dx, amplification, MAX_NEWTON_ITERATIONS);
+            assert!(dy > Decimal::zero());
+            assert!(dy < y);
+
+            let d_after: Decimal = compute_d(x + dx, y - dy, amplification);
+            assert!((d_after - d).abs() <= dec!("0.000000001"), "D moved from {} to {}", d, d_after);
+        }
+    }
+
+    #[test]
+    fn compute_spot_price_of_balanced_reserves_is_one() {
+        let d: Decimal = compute_d(dec!("1000"), dec!("1000"), dec!("100"));
+        let price: Decimal = compute_spot_price(dec!("1000"), dec!("1000"), d, dec!("100"));
+        assert!((price - Decimal::one()).abs() <= dec!("0.000000000001"), "Spot price of {} is not 1", price);
+    }
+}
@@ -98,4 +98,333 @@ pub fn address_pair_symbol(address1: ResourceAddress, address2: ResourceAddress)
 
     // Format the names and return them.
     return format!("{}-{}", names.0, names.1);
-}
\ No newline at end of file
+}
+
+/// Calculates the price impact of a trade as a percentage.
+/// 
+/// # Arguments:
+/// 
+/// * `spot_price` (Decimal) - The price before the trade, in output tokens per input token.
+/// * `effective_price` (Decimal) - The price actually paid in the trade, in output tokens per input token.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The percentage by which the effective price is worse than the spot price, or zero if there is no spot
+/// price to compare against.
+pub fn price_impact(spot_price: Decimal, effective_price: Decimal) -> Decimal {
+    if spot_price == Decimal::zero() {
+        return Decimal::zero();
+    }
+    return (spot_price - effective_price) / spot_price * dec!("100");
+}
+
+/// Asserts that the deadline of a transaction has not passed.
+/// 
+/// Transactions may be executed a long time after they were signed, by which point the prices they were made for may
+/// be long gone. A deadline makes the transaction fail instead if it's executed after the given epoch.
+/// 
+/// # Arguments:
+/// 
+/// * `deadline_epoch` (Option<u64>) - The last epoch that the transaction may be executed in, or `None` for no 
+/// deadline.
+/// * `label` (String) - The label of the method that called this assert method.
+pub fn assert_deadline(deadline_epoch: Option<u64>, label: String) {
+    if let Some(deadline_epoch) = deadline_epoch {
+        let current_epoch: u64 = Runtime::current_epoch();
+        assert!(
+            current_epoch <= deadline_epoch,
+            "[{}]: The deadline of epoch {} has passed, the current epoch is {}.",
+            label, deadline_epoch, current_epoch
+        );
+    }
+}
+
+/// Sorts the given addresses in the same way as `sort_addresses` and returns them.
+/// 
+/// # Arguments:
+/// 
+/// * `addresses` (Vec<ResourceAddress>) - The resource addresses to sort.
+/// 
+/// # Returns:
+/// 
+/// * `Vec<ResourceAddress>` - A vector of the addresses passed after they had been sorted.
+/// 
+/// # Notes:
+/// 
+/// This is the equivalent of `sort_addresses` for token sets of any size and is used for the keys of the hashmap 
+/// storing the weighted pools. Just like `sort_addresses`, the only thing that matters is that it is deterministic.
+pub fn sort_address_set(mut addresses: Vec<ResourceAddress>) -> Vec<ResourceAddress> {
+    addresses.sort_by(|address1, address2| address2.to_vec().cmp(&address1.to_vec()));
+    return addresses;
+}
+
+/// Creates a symbol for the given set of addresses.
+/// 
+/// This works in the same way as `address_pair_symbol` but for any number of tokens. The symbol of each token is
+/// loaded from its metadata and if no symbol is found then its resource address is used instead.
+/// 
+/// # Arguments:
+/// 
+/// * `addresses` (Vec<ResourceAddress>) - The resource addresses of the tokens.
+/// 
+/// # Returns:
+/// 
+/// `String` - A string of the symbols of the tokens joined by dashes.
+pub fn address_set_symbol(addresses: Vec<ResourceAddress>) -> String {
+    return sort_address_set(addresses)
+        .into_iter()
+        .map(|address| match borrow_resource_manager!(address).metadata().get_string("symbol") {
+            Ok(s) => format!("{}", s),
+            Err(_e) => format!("{:?} has no symbol", address)
+        })
+        .collect::<Vec<String>>()
+        .join("-");
+}
+
+/// The natural logarithm of two, used to reduce the arguments of `ln` and `exp`.
+fn ln_2() -> Decimal {
+    return dec!("0.693147180559945309");
+}
+
+/// Calculates the natural logarithm of the given number.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - A positive number.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The natural logarithm of `x`.
+/// 
+/// # Notes:
+/// 
+/// `x` is first reduced to `m * 2^k` where `m` is in `[1, 2)` so that `ln(x) = k * ln(2) + ln(m)`. The logarithm of `m`
+/// is then calculated through the series `ln(m) = 2 * (z + z^3 / 3 + z^5 / 5 + ...)` where `z = (m - 1) / (m + 1)`. 
+/// Since `z` is at most `1/3`, every term is at least nine times smaller than the one before it and the series is 
+/// summed until the terms are too small to be represented.
+pub fn ln(x: Decimal) -> Decimal {
+    assert!(x > Decimal::zero(), "[ln]: The logarithm is only defined for positive numbers.");
+
+    let mut m: Decimal = x;
+    let mut k: Decimal = Decimal::zero();
+    while m >= dec!("2") {
+        m = m / dec!("2");
+        k = k + Decimal::one();
+    }
+    while m < Decimal::one() {
+        m = m * dec!("2");
+        k = k - Decimal::one();
+    }
+
+    let z: Decimal = (m - Decimal::one()) / (m + Decimal::one());
+    let z_squared: Decimal = z * z;
+    let mut term: Decimal = z;
+    let mut n: Decimal = Decimal::one();
+    let mut sum: Decimal = Decimal::zero();
+    while term != Decimal::zero() {
+        sum = sum + term / n;
+        term = term * z_squared;
+        n = n + dec!("2");
+    }
+
+    return k * ln_2() + sum * dec!("2");
+}
+
+/// Calculates `e` raised to the power of the given number.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - The exponent.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The value of `e^x`.
+/// 
+/// # Notes:
+/// 
+/// `x` is first reduced to `k * ln(2) + r` where `r` is in `[0, ln(2))` so that `e^x = 2^k * e^r`. `e^r` is then 
+/// calculated through its Taylor series which is summed until the terms are too small to be represented. Negative 
+/// exponents are calculated as `1 / e^-x`.
+pub fn exp(x: Decimal) -> Decimal {
+    if x < Decimal::zero() {
+        return Decimal::one() / exp(Decimal::zero() - x);
+    }
+
+    let mut r: Decimal = x;
+    let mut k: u32 = 0;
+    while r >= ln_2() {
+        r = r - ln_2();
+        k = k + 1;
+    }
+
+    let mut term: Decimal = Decimal::one();
+    let mut n: Decimal = Decimal::one();
+    let mut sum: Decimal = Decimal::one();
+    while term != Decimal::zero() {
+        term = term * r / n;
+        sum = sum + term;
+        n = n + Decimal::one();
+    }
+
+    for _ in 0..k {
+        sum = sum * dec!("2");
+    }
+    return sum;
+}
+
+/// Raises the given base to the power of the given exponent.
+/// 
+/// # Arguments:
+/// 
+/// * `base` (Decimal) - A non-negative base.
+/// * `exponent` (Decimal) - The exponent, which does not have to be an integer.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The value of `base^exponent` calculated as `e^(exponent * ln(base))`.
+pub fn pow(base: Decimal, exponent: Decimal) -> Decimal {
+    if exponent == Decimal::zero() {
+        return Decimal::one();
+    }
+    if base == Decimal::zero() {
+        return Decimal::zero();
+    }
+    return exp(exponent * ln(base));
+}
+
+/// Raises the given base to the power of the given integer exponent.
+/// 
+/// # Arguments:
+/// 
+/// * `base` (Decimal) - A non-zero base.
+/// * `exponent` (i64) - The integer exponent, which may be negative.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The value of `base^exponent` calculated through exponentiation by squaring.
+pub fn powi(base: Decimal, exponent: i64) -> Decimal {
+    if exponent < 0 {
+        return Decimal::one() / powi(base, -exponent);
+    }
+
+    let mut result: Decimal = Decimal::one();
+    let mut square: Decimal = base;
+    let mut remaining: i64 = exponent;
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            result = result * square;
+        }
+        remaining = remaining / 2;
+        if remaining > 0 {
+            square = square * square;
+        }
+    }
+    return result;
+}
+
+/// Calculates the square root of the given number.
+/// 
+/// # Arguments:
+/// 
+/// * `x` (Decimal) - A non-negative number.
+/// 
+/// # Returns:
+/// 
+/// * `Decimal` - The square root of `x` found through Newton's method.
+pub fn sqrt(x: Decimal) -> Decimal {
+    assert!(x >= Decimal::zero(), "[sqrt]: The square root is only defined for non-negative numbers.");
+    if x == Decimal::zero() {
+        return Decimal::zero();
+    }
+
+    let mut y: Decimal = if x > Decimal::one() { x } else { Decimal::one() };
+    for _ in 0..255 {
+        let y_previous: Decimal = y;
+        y = (y + x / y) / dec!("2");
+        if (y - y_previous).abs() <= dec!("0.000000000000000001") {
+            break;
+        }
+    }
+    return y;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that the given value is within the given relative tolerance of the expected value.
+    fn assert_close(actual: Decimal, expected: Decimal, tolerance: Decimal) {
+        let scale: Decimal = if expected.abs() > Decimal::one() { expected.abs() } else { Decimal::one() };
+        assert!(
+            (actual - expected).abs() <= tolerance * scale,
+            "{} is not within {} of {}", actual, tolerance, expected
+        );
+    }
+
+    #[test]
+    fn ln_and_exp_of_known_values() {
+        assert_eq!(ln(Decimal::one()), Decimal::zero());
+        assert_eq!(exp(Decimal::zero()), Decimal::one());
+        assert_close(exp(Decimal::one()), dec!("2.718281828459045235"), dec!("0.000000000001"));
+        assert_close(ln(dec!("2.718281828459045235")), Decimal::one(), dec!("0.000000000001"));
+        assert_close(ln(dec!("2")), ln_2(), dec!("0.000000000001"));
+    }
+
+    #[test]
+    fn exp_of_ln_round_trips() {
+        for x in [dec!("0.001"), dec!("0.5"), dec!("1"), dec!("1.5"), dec!("2"), dec!("10"), dec!("123456.789")] {
+            assert_close(exp(ln(x)), x, dec!("0.000000000001"));
+        }
+    }
+
+    #[test]
+    fn ln_of_exp_round_trips() {
+        for x in [dec!("-5"), dec!("-1"), dec!("0.3"), dec!("1"), dec!("7.5"), dec!("20")] {
+            assert_close(ln(exp(x)), x, dec!("0.000000000001"));
+        }
+    }
+
+    #[test]
+    fn pow_at_80_20_weights() {
+        assert_close(pow(dec!("2"), dec!("0.8")), dec!("1.741101126592248278"), dec!("0.000000000001"));
+        assert_close(pow(dec!("0.5"), dec!("0.2")), dec!("0.870550563296124139"), dec!("0.000000000001"));
+        assert_close(pow(dec!("3"), dec!("0.8")) * pow(dec!("3"), dec!("0.2")), dec!("3"), dec!("0.000000000001"));
+
+        // The output of swapping 100 of the 80% token into a pool with reserves of 1000 of each token is 
+        // `1000 * (1 - (1000 / 1100)^(0.8 / 0.2))`.
+        let output_amount: Decimal = dec!("1000") 
+            * (Decimal::one() - pow(dec!("1000") / dec!("1100"), dec!("0.8") / dec!("0.2")));
+        assert_close(output_amount, dec!("316.986544634929308107"), dec!("0.000000000001"));
+    }
+
+    #[test]
+    fn pow_of_zero_exponent_and_base() {
+        assert_eq!(pow(dec!("5"), Decimal::zero()), Decimal::one());
+        assert_eq!(pow(Decimal::zero(), dec!("0.8")), Decimal::zero());
+    }
+
+    #[test]
+    fn powi_of_known_values() {
+        assert_eq!(powi(dec!("2"), 10), dec!("1024"));
+        assert_eq!(powi(dec!("2"), -2), dec!("0.25"));
+        assert_eq!(powi(dec!("7.5"), 0), Decimal::one());
+        assert_eq!(powi(dec!("1.5"), 3), dec!("3.375"));
+        assert_close(powi(dec!("1.0001"), 10000), dec!("2.718145926825224864"), dec!("0.000000000001"));
+        assert_close(
+            powi(dec!("1.0001"), -10000) * powi(dec!("1.0001"), 10000), 
+            Decimal::one(), 
+            dec!("0.000000000001")
+        );
+    }
+
+    #[test]
+    fn sqrt_of_known_values() {
+        assert_eq!(sqrt(Decimal::zero()), Decimal::zero());
+        assert_close(sqrt(dec!("4")), dec!("2"), dec!("0.000000000001"));
+        assert_close(sqrt(dec!("0.0001")), dec!("0.01"), dec!("0.000000000001"));
+        assert_close(sqrt(dec!("2")), dec!("1.414213562373095048"), dec!("0.000000000001"));
+        for x in [dec!("0.3"), dec!("1"), dec!("1000"), dec!("123456789.123")] {
+            assert_close(sqrt(x) * sqrt(x), x, dec!("0.000000000001"));
+        }
+    }
+}
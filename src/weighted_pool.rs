@@ -0,0 +1,859 @@
+use scrypto::prelude::*;
+use crate::utils::*;
+
+/// The margin that the fractional powers of a swap are rounded by in favour of the pool. `pow` is calculated through 
+/// the series of `ln` and `exp`, whose errors are far smaller than this margin, so rounding by it keeps a swap from
+/// paying out more than the constant mean invariant allows.
+fn pow_margin() -> Decimal {
+    return dec!("0.00000000000001");
+}
+
+#[blueprint]
+mod weighted_pool {
+
+    /// WeightedPool encapsulate liquidity pool fields and methods for pools of two or more tokens with custom weights
+    /// Uses the constant mean invariant : prod(b_i ^ w_i) = k, where b_i is the reserves of token i and w_i is its
+    /// weight. A pool with weights of 50/50 behaves exactly like the constant product `Pool`.
+    pub struct WeightedPool{
+       /// use a more flexible dynamic way to store the vault addresses.
+       /// Note: WeightedPool object has one vault per token in the pool
+       vaults: HashMap<ResourceAddress, Vault>,
+
+       /// The normalized weight of each one of the tokens in the pool. The weights of all of the tokens
+       /// add up to 1 and represent the share of the value of the pool that is held in each token.
+       weights: HashMap<ResourceAddress, Decimal>,
+
+       /// Tracking token is used keep track of the ratio user's contribution
+       /// proportional to the total pool amount. This ratio will be used to calculate
+       /// fees distributed and also for when user withdraws their liquidity out of the pool
+       tracking_token_address: ResourceAddress,
+
+       /// Admin badge used to mint and burn tracking token for this pool
+       tracking_token_admin_badge: Vault,
+
+       /// Decimal Amount between 0 to 100 representing the percentage fee
+       /// paid to liquidity pool (to be distributed to the liquidity providers
+       /// based on thier LP tracking token ratio )
+       fee_to_pool: Decimal,
+
+       /// Decimal Amount between 0 to 100 representing the percentage of the swap 
+       /// fee which is diverted to the protocol instead of the liquidity providers
+       protocol_fee: Decimal,
+
+       /// Vaults holding the protocol fees collected from swaps until they're collected
+       protocol_vaults: HashMap<ResourceAddress, Vault>,
+
+       /// When the pool is paused no swaps can be made and no liquidity can be added,
+       /// but liquidity providers are still able to remove their liquidity
+       paused: bool
+    }
+
+    impl WeightedPool {
+        /// Creates a new weighted pool based on two or more resources, their weights and fee amount to go to the pool
+        /// validations include:
+        ///  - Check there are between 2 and 8 tokens and exactly one weight per token
+        ///  - Check the resource addresses are all different
+        ///  - Check resources are all fungible
+        ///  - Check the input token buckets are not empty
+        ///  - Check every weight is positive
+        ///  - Check every normalized weight is at least 1%
+        ///  - Check fee amount set is decimal between 0 to 100, excluding 100
+        /// Returns LP Tracking Token (for the initial liquidity provider
+        /// Note: weights are normalized so that they add up to 1, so 80/20 and 0.8/0.2 are the same pool
+        pub fn instantiate_pool(
+            tokens: Vec<Bucket>,
+            weights: Vec<Decimal>,
+            fee_to_pool: Decimal) -> (WeightedPoolComponent, Bucket) {
+            // Check there are between 2 and 8 tokens and exactly one weight per token
+            assert!(
+                (tokens.len() >= 2) & (tokens.len() <= 8),
+                "[Weighted Pool Creation]: Weighted pools must have between 2 and 8 tokens."
+            );
+            assert_eq!(
+                tokens.len(), weights.len(),
+                "[Weighted Pool Creation]: There must be exactly one weight for every token."
+            );
+
+            // Check token addresses are all different
+            let addresses: Vec<ResourceAddress> = tokens.iter().map(|token| token.resource_address()).collect();
+            let unique_addresses: HashSet<ResourceAddress> = addresses.iter().cloned().collect();
+            assert_eq!(
+                unique_addresses.len(), addresses.len(),
+                "[Weighted Pool Creation]: Liquidity pools may only be created between different tokens."
+            );
+
+            for token in tokens.iter() {
+                // Check resources neither is Non-Fungible
+                assert_eq!(
+                    borrow_resource_manager!(token.resource_address()).resource_type().is_fungible(), true,
+                    "[Weighted Pool Creation]: All assets must be fungible."
+                );
+
+                // Check the input token buckets are not empty
+                assert!(!token.is_empty(), "[Weighted Pool Creation]: Can't create a pool from an empty bucket.");
+            }
+
+            // Check every weight is positive, before they're normalized by their total
+            for weight in weights.iter() {
+                assert!(*weight > Decimal::zero(), "[Weighted Pool Creation]: Every weight must be positive.");
+            }
+
+            // Check every normalized weight is at least 1%
+            let total_weight: Decimal = weights.iter().fold(Decimal::zero(), |total, weight| total + *weight);
+            let normalized_weights: Vec<Decimal> = weights.iter().map(|weight| *weight / total_weight).collect();
+            for weight in normalized_weights.iter() {
+                assert!(
+                    *weight >= dec!("0.01"),
+                    "[Weighted Pool Creation]: Every token must have a weight of at least 1%."
+                );
+            }
+
+            // Check fee amount set is decimal between 0 to 100, excluding 100 since no input would be left to swap with
+            assert!(
+                (fee_to_pool >= Decimal::zero()) & (fee_to_pool < dec!("100")),
+                "[Weighted Pool Creation]: Fee must be between 0 and 100, excluding 100"
+            );
+
+            // Validation is done
+            let lp_id: String = sort_address_set(addresses.clone())
+                .iter()
+                .map(|address| format!("{:?}", address))
+                .collect::<Vec<String>>()
+                .join("-");
+            let pool_name: String = address_set_symbol(addresses.clone());
+
+            info!(
+                "[Weighted Pool Creation]: Creating new pool between tokens: {}, of name: {}, Weights: {:?}",
+                lp_id, pool_name, normalized_weights
+            );
+
+            let mut vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            let mut protocol_vaults: HashMap<ResourceAddress, Vault> = HashMap::new();
+            let mut weights_map: HashMap<ResourceAddress, Decimal> = HashMap::new();
+            for (token, weight) in tokens.into_iter().zip(normalized_weights.into_iter()) {
+                weights_map.insert(token.resource_address(), weight);
+                protocol_vaults.insert(token.resource_address(), Vault::new(token.resource_address()));
+                vaults.insert(token.resource_address(), Vault::with_bucket(token));
+            }
+
+            // Create Admin badge to give authority for minting and burning LP tracking tokens
+            let tracking_token_admin_badge: Bucket = ResourceBuilder::new_fungible()
+            .divisibility(DIVISIBILITY_NONE)
+            .metadata("name", "Tracking Token Admin Badge")
+            .metadata("symbol", "TTAB")
+            .metadata("description", "This is an admin badge that has the authority to mint and burn tracking tokens")
+            .metadata("lp_id", format!("{}", lp_id))
+            .mint_initial_supply(1);
+
+            // Creating the tracking tokens and minting the amount owed to the initial liquidity provider
+            let tracking_tokens: Bucket = ResourceBuilder::new_fungible()
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .metadata("name", format!("{} Weighted LP Tracking Token", pool_name))
+                .metadata("symbol", "TT")
+                .metadata("description", "A tracking token used to track the percentage ownership of liquidity providers over the weighted liquidity pool")
+                .metadata("lp_id", format!("{}", lp_id))
+                .mintable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .burnable(rule!(require(tracking_token_admin_badge.resource_address())), LOCKED)
+                .mint_initial_supply(100);
+
+            // Creating the liquidity pool component and instantiating it
+            let liquidity_pool = Self {
+                vaults: vaults,
+                weights: weights_map,
+                tracking_token_address: tracking_tokens.resource_address(),
+                tracking_token_admin_badge: Vault::with_bucket(tracking_token_admin_badge),
+                fee_to_pool: fee_to_pool,
+                protocol_fee: Decimal::zero(),
+                protocol_vaults: protocol_vaults,
+                paused: false
+            }
+            .instantiate()
+            // .globalize() NOTE: comment out if running manifests under `./manifests/pool` and using setup_pool_test.sh
+            ;
+
+            return (liquidity_pool, tracking_tokens);
+        }
+
+        /// Checks if the given address belongs to this pool or not.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `bool` - A boolean of whether the address belongs to this pool or not.
+        pub fn belongs_to_pool(
+            &self,
+            address: ResourceAddress
+        ) -> bool {
+            return self.vaults.contains_key(&address);
+        }
+
+        /// Asserts that the given address belongs to the pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `address` (ResourceAddress) - The address of the resource that we wish to check if it belongs to the pool.
+        /// * `label` (String) - The label of the method that called this assert method.
+        pub fn assert_belongs_to_pool(
+            &self,
+            address: ResourceAddress,
+            label: String
+        ) {
+            assert!(
+                self.belongs_to_pool(address),
+                "[{}]: The provided resource address does not belong to the pool.",
+                label
+            );
+        }
+
+        /// Gets the resource addresses of the tokens in this liquidity pool sorted through `sort_address_set`.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Vec<ResourceAddress>` - A vector of the sorted resource addresses of the tokens in this liquidity pool.
+        pub fn addresses(&self) -> Vec<ResourceAddress> {
+            return sort_address_set(self.vaults.keys().cloned().collect::<Vec<ResourceAddress>>());
+        }
+
+        /// Gets the name of the given liquidity pool from the symbols of its tokens.
+        /// 
+        /// # Returns:
+        /// 
+        /// `String` - A string of the symbols of the tokens
+        pub fn name(&self) -> String {
+            return address_set_symbol(self.addresses());
+        }
+
+        /// Gets the resource address of the tracking tokens of this liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// `ResourceAddress` - The resource address of the tracking tokens.
+        pub fn tracking_token_address(&self) -> ResourceAddress {
+            return self.tracking_token_address;
+        }
+
+        /// Gets the percentage fee paid to this liquidity pool on swaps.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the fee paid to the pool.
+        pub fn fee_to_pool(&self) -> Decimal {
+            return self.fee_to_pool;
+        }
+
+        /// Gets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - A decimal between 0 and 100 of the share of the swap fee paid to the protocol.
+        pub fn protocol_fee(&self) -> Decimal {
+            return self.protocol_fee;
+        }
+
+        /// Sets the percentage of the swap fee which is diverted to the protocol.
+        /// 
+        /// This method performs a number of checks before the protocol fee is set:
+        /// 
+        /// * **Check 1:** Checks that the protocol fee is a decimal between 0 and 100.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `protocol_fee` (Decimal) - The percentage of the swap fee to divert to the protocol.
+        pub fn set_protocol_fee(
+            &mut self,
+            protocol_fee: Decimal
+        ) {
+            assert!(
+                (protocol_fee >= Decimal::zero()) & (protocol_fee <= dec!("100")), 
+                "[Weighted Set Protocol Fee]: Protocol fee must be between 0 and 100"
+            );
+            info!("[Weighted Set Protocol Fee]: Protocol fee set from {} to {}", self.protocol_fee, protocol_fee);
+
+            self.protocol_fee = protocol_fee;
+        }
+
+        /// Withdraws all of the protocol fees collected by this weighted pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the protocol fees of each of the tokens.
+        pub fn collect_protocol_fees(&mut self) -> Vec<Bucket> {
+            return self.protocol_vaults
+                .values_mut()
+                .map(|vault| vault.take_all())
+                .collect::<Vec<Bucket>>();
+        }
+
+        /// Takes the share of the fee of a swap which goes to the protocol out of the input tokens and puts it into the
+        /// protocol vaults. The output of the swap is not affected by this, only less of the fee is deposited into the
+        /// pool for the liquidity providers.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (&mut Bucket) - A bucket of the input tokens of a swap.
+        fn take_protocol_fee(
+            &mut self,
+            tokens: &mut Bucket
+        ) {
+            let protocol_amount: Decimal = tokens.amount() * self.fee_to_pool / dec!("100") 
+                * self.protocol_fee / dec!("100");
+            if protocol_amount > Decimal::zero() {
+                info!(
+                    "[Weighted Protocol Fee]: Taking {} of {:?} as protocol fee", 
+                    protocol_amount, tokens.resource_address()
+                );
+                self.protocol_vaults.get_mut(&tokens.resource_address()).unwrap().put(tokens.take(protocol_amount));
+            }
+        }
+
+        /// Gets whether this weighted pool is paused or not.
+        /// 
+        /// # Returns:
+        /// 
+        /// `bool` - A boolean of whether the pool is paused.
+        pub fn paused(&self) -> bool {
+            return self.paused;
+        }
+
+        /// Pauses or unpauses this weighted pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `paused` (bool) - Whether the pool should be paused.
+        pub fn set_paused(
+            &mut self,
+            paused: bool
+        ) {
+            info!("[Weighted Set Paused]: Pool paused set from {} to {}", self.paused, paused);
+            self.paused = paused;
+        }
+
+        /// Asserts that this weighted pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_not_paused(
+            &self,
+            label: String
+        ) {
+            assert!(!self.paused, "[{}]: The weighted pool is paused.", label);
+        }
+
+        /// Gets the normalized weight of the given token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the weight of.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The weight of the token, where the weights of all of the tokens add up to 1.
+        pub fn weight(
+            &self,
+            resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(resource_address, String::from("Weighted Weight"));
+
+            return self.weights[&resource_address];
+        }
+
+        /// Gets the amount of reserves of the given token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The resource address of the token to get the reserves of.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The amount of the given token in the vaults of this liquidity pool.
+        pub fn reserve(
+            &self,
+            resource_address: ResourceAddress
+        ) -> Decimal {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(resource_address, String::from("Weighted Reserve"));
+
+            return self.vaults[&resource_address].amount();
+        }
+
+        /// Calculates the value of the constant mean invariant: `prod(b_i ^ w_i)`.
+        /// 
+        /// # Returns:
+        /// 
+        /// `Decimal` - The product of the reserves of every token raised to the power of its weight.
+        pub fn invariant(&self) -> Decimal {
+            return self.addresses()
+                .iter()
+                .fold(Decimal::one(), |product, address| product * pow(self.vaults[address].amount(), self.weights[address]));
+        }
+
+        /// Asserts that the given input and output tokens both belong to this pool and are not the same token.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `label` (String) - The label of the method that called this assert method.
+        fn assert_valid_pair(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            label: String
+        ) {
+            self.assert_belongs_to_pool(input_resource_address, label.clone());
+            self.assert_belongs_to_pool(output_resource_address, label.clone());
+            assert_ne!(
+                input_resource_address, output_resource_address,
+                "[{}]: The input and output tokens may not be the same.",
+                label
+            );
+        }
+
+        /// Calculates the spot price of the input token in terms of the output token in this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the token to get the price of.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token the price is given in.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The amount of output tokens that one input token is worth at the current reserves, not
+        /// taking the fee or the price impact into account. This is `(y / wy) / (x / wx)`.
+        pub fn spot_price(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress
+        ) -> Decimal {
+            self.assert_valid_pair(input_resource_address, output_resource_address, String::from("Weighted Spot Price"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&output_resource_address].amount();
+            return (y / self.weights[&output_resource_address]) / (x / self.weights[&input_resource_address]);
+        }
+
+        /// This method calculates the amount of output tokens that would be received for a given amount of an input
+        /// token. This is calculated through the constant mean invariant.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that both tokens belong to this liquidity pool and are not the same.
+        /// * **Check 2:** Checks that the input amount is at most 30% of the reserves of the input token.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `input_amount` (Decimal) - The amount of input tokens to calculate the output for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The output amount for the given input.
+        /// 
+        /// # Note:
+        /// 
+        /// This method is equivalent to finding `dy` in the equation `(x + rdx)^wx * (y - dy)^wy = x^wx * y^wy` which
+        /// gives `dy = y * (1 - (x / (x + rdx))^(wx / wy))`, where `r = (100 - fee) / 100` is the fee modifier. The
+        /// limit on the input amount keeps the swap in the range where the fractional powers are accurate, and the 
+        /// power is rounded up by `pow_margin` so that `dy` is always rounded down.
+        pub fn calculate_output_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            input_amount: Decimal
+        ) -> Decimal {
+            self.assert_valid_pair(input_resource_address, output_resource_address, String::from("Weighted Calculate Output"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&output_resource_address].amount();
+            let wx: Decimal = self.weights[&input_resource_address];
+            let wy: Decimal = self.weights[&output_resource_address];
+            let dx: Decimal = input_amount;
+            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+            assert!(
+                dx <= x * dec!("0.3"),
+                "[Weighted Calculate Output]: The input amount may not exceed 30% of the reserves."
+            );
+
+            let dy: Decimal = y * (Decimal::one() - pow(x / (x + r * dx), wx / wy) - pow_margin());
+            return if dy > Decimal::zero() { dy } else { Decimal::zero() };
+        }
+
+        /// This method calculates the amount of input tokens that would be required to receive the specified amount of
+        /// output tokens. This is calculated through the constant mean invariant.
+        /// 
+        /// This method performs a number of checks before the calculation is done:
+        /// 
+        /// * **Check 1:** Checks that both tokens belong to this liquidity pool and are not the same.
+        /// * **Check 2:** Checks that the output amount is at most 30% of the reserves of the output token.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `input_resource_address` (ResourceAddress) - The resource address of the input token.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the output token.
+        /// * `output_amount` (Decimal) - The amount of output tokens to calculate the input for.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Decimal` - The input amount for the given output.
+        /// 
+        /// # Note:
+        /// 
+        /// This method is equivalent to finding `dx` in the equation `(x + rdx)^wx * (y - dy)^wy = x^wx * y^wy` which
+        /// gives `dx = x * ((y / (y - dy))^(wy / wx) - 1) / r`, where `r = (100 - fee) / 100` is the fee modifier. The
+        /// power is rounded up by `pow_margin` so that `dx` is always rounded up.
+        pub fn calculate_input_amount(
+            &self,
+            input_resource_address: ResourceAddress,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal
+        ) -> Decimal {
+            self.assert_valid_pair(input_resource_address, output_resource_address, String::from("Weighted Calculate Input"));
+
+            let x: Decimal = self.vaults[&input_resource_address].amount();
+            let y: Decimal = self.vaults[&output_resource_address].amount();
+            let wx: Decimal = self.weights[&input_resource_address];
+            let wy: Decimal = self.weights[&output_resource_address];
+            let dy: Decimal = output_amount;
+            let r: Decimal = (dec!("100") - self.fee_to_pool) / dec!("100");
+            assert!(
+                dy <= y * dec!("0.3"),
+                "[Weighted Calculate Input]: The output amount may not exceed 30% of the reserves."
+            );
+
+            let dx: Decimal = x * (pow(y / (y - dy), wy / wx) + pow_margin() - Decimal::one()) / r;
+            return dx;
+        }
+
+        /// Deposits a bucket of tokens into this liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `bucket` (Bucket) - A buckets of the tokens to deposit into the liquidity pool
+        fn deposit(
+            &mut self,
+            bucket: Bucket
+        ) {
+            // Checking if the passed resource address belongs to this pool.
+            self.assert_belongs_to_pool(bucket.resource_address(), String::from("Weighted Deposit"));
+
+            self.vaults.get_mut(&bucket.resource_address()).unwrap().put(bucket);
+        }
+
+        /// Withdraws tokens from the liquidity pool.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `resource_address` (ResourceAddress) - The address of the resource to withdraw from the liquidity pool.
+        /// * `amount` (Decimal) - The amount of tokens to withdraw from the liquidity pool.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the withdrawn tokens.
+        fn withdraw(
+            &mut self,
+            resource_address: ResourceAddress,
+            amount: Decimal
+        ) -> Bucket {
+            // Performing the checks to ensure tha the withdraw can actually go through
+            self.assert_belongs_to_pool(resource_address, String::from("Weighted Withdraw"));
+
+            // Getting the vault of that resource and checking if there is enough liquidity to perform the withdraw.
+            let vault: &mut Vault = self.vaults.get_mut(&resource_address).unwrap();
+            assert!(
+                vault.amount() >= amount,
+                "[Weighted Withdraw]: Not enough liquidity available for the withdraw."
+            );
+
+            return vault.take(amount);
+        }
+
+        /// Adds liquidity to this liquidity pool in exchange for liquidity provider tracking tokens.
+        /// 
+        /// Liquidity is added in the ratio of the current reserves so that the weights of the pool are not disturbed.
+        /// The token which was supplied in the smallest proportion to its reserves determines how much of every other
+        /// token is deposited, and the excess of the other tokens is returned to the liquidity provider.
+        /// 
+        /// This method performs a number of checks before liquidity is added to the pool:
+        /// 
+        /// * **Check 1:** Checks that the weighted pool is not paused.
+        /// * **Check 2:** Checks that exactly one bucket is passed for every token in the pool.
+        /// * **Check 3:** Checks that the buckets passed are not empty.
+        /// * **Check 4:** Checks that the minimum deposits are only given for tokens in the pool.
+        /// * **Check 5:** Checks that the amounts deposited and the tracking tokens minted are not below the minimums.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Vec<Bucket>) - A vector of buckets, one for each of the tokens in the pool.
+        /// * `min_tracking_tokens_out` (Decimal) - The minimum amount of tracking tokens that the liquidity provider is
+        /// willing to accept before the method fails.
+        /// * `min_deposits` (HashMap<ResourceAddress, Decimal>) - The minimum amount of each token that must be 
+        /// deposited, keyed by the resource address of the token. Tokens without a minimum may be left out.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the remaining tokens, in the same order as they were passed.
+        /// * `Bucket` - A bucket of the tracking tokens issued to the liquidity provider.
+        pub fn add_liquidity(
+            &mut self,
+            mut tokens: Vec<Bucket>,
+            min_tracking_tokens_out: Decimal,
+            min_deposits: HashMap<ResourceAddress, Decimal>
+        ) -> (Vec<Bucket>, Bucket) {
+            // Checking that the pool is not paused and that exactly one bucket is passed for every token in the pool.
+            self.assert_not_paused(String::from("Weighted Add Liquidity"));
+            let addresses: Vec<ResourceAddress> = tokens.iter().map(|token| token.resource_address()).collect();
+            assert_eq!(
+                sort_address_set(addresses.clone()), self.addresses(),
+                "[Weighted Add Liquidity]: Exactly one bucket must be passed for every token in the pool."
+            );
+
+            // Checking that the buckets passed are not empty
+            for token in tokens.iter() {
+                assert!(!token.is_empty(), "[Weighted Add Liquidity]: Can not add liquidity from an empty bucket");
+            }
+            for address in min_deposits.keys() {
+                self.assert_belongs_to_pool(*address, String::from("Weighted Add Liquidity"));
+            }
+
+            // Computing the smallest ratio of supplied tokens to reserves, which is the ratio at which all of the
+            // tokens are deposited. In the case that the liquidity pool has been completely emptied out, all of the
+            // tokens are deposited as they are.
+            let tracking_tokens_manager: ResourceManager = borrow_resource_manager!(self.tracking_token_address);
+            let total_supply: Decimal = tracking_tokens_manager.total_supply();
+            let ratio: Option<Decimal> = if total_supply == Decimal::zero() {
+                None
+            } else {
+                tokens.iter()
+                    .map(|token| token.amount() / self.vaults[&token.resource_address()].amount())
+                    .min()
+            };
+            info!("[Weighted Add Liquidity]: Ratio of liquidity to add: {:?}", ratio);
+
+            // Depositing the amount of tokens calculated into the liquidity pool
+            for token in tokens.iter_mut() {
+                let amount: Decimal = match ratio {
+                    Some(ratio) => self.vaults[&token.resource_address()].amount() * ratio,
+                    None => token.amount()
+                };
+                let min_amount: Decimal = min_deposits.get(&token.resource_address()).cloned().unwrap_or(Decimal::zero());
+                assert!(
+                    amount >= min_amount,
+                    "[Weighted Add Liquidity]: The amount of {:?} deposited ({}) is below the minimum of {}. The \
+                    reserve ratio may have moved.",
+                    token.resource_address(), amount, min_amount
+                );
+                self.deposit(token.take(amount));
+            }
+
+            // Computing the amount of tracking tokens that the liquidity provider is owed and minting them.
+            let tracking_amount: Decimal = match ratio {
+                Some(ratio) => total_supply * ratio,
+                None => dec!("100.00")
+            };
+            let tracking_tokens: Bucket = self.tracking_token_admin_badge.authorize(|| {
+                tracking_tokens_manager.mint(tracking_amount)
+            });
+            info!("[Weighted Add Liquidity]: Owed amount of tracking tokens: {}", tracking_amount);
+            assert!(
+                tracking_amount >= min_tracking_tokens_out,
+                "[Weighted Add Liquidity]: Tracking tokens minted ({}) are below the minimum of {}.",
+                tracking_amount, min_tracking_tokens_out
+            );
+
+            // Returning the remaining tokens and the tracking tokens
+            return (tokens, tracking_tokens);
+        }
+
+        /// Removes the percentage of the liquidity owed to this liquidity provider.
+        /// 
+        /// This method performs a number of checks before liquidity removed from the pool:
+        /// 
+        /// * **Check 1:** Checks to ensure that the tracking tokens passed do indeed belong to this liquidity pool.
+        /// * **Check 2:** Checks that the minimum amounts are only given for tokens in the pool.
+        /// * **Check 3:** Checks that the amounts withdrawn are not below the minimums.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tracking_tokens` (Bucket) - A bucket of the tracking tokens that the liquidity provider wishes to
+        /// exchange for their share of the liquidity.
+        /// * `min_amounts` (HashMap<ResourceAddress, Decimal>) - The minimum amount of each token that the liquidity 
+        /// provider is willing to accept before the method fails, keyed by the resource address of the token. Tokens 
+        /// without a minimum may be left out.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Vec<Bucket>` - A vector of buckets of the share of the liquidity provider of each token, in the order of
+        /// `addresses`.
+        pub fn remove_liquidity(
+            &mut self,
+            tracking_tokens: Bucket,
+            min_amounts: HashMap<ResourceAddress, Decimal>
+        ) -> Vec<Bucket> {
+            // Checking the resource address of the tracking tokens passed to ensure that they do indeed belong to this
+            // liquidity pool.
+            assert_eq!(
+                tracking_tokens.resource_address(), self.tracking_token_address,
+                "[Weighted Remove Liquidity]: The tracking tokens given do not belong to this liquidity pool."
+            );
+            for address in min_amounts.keys() {
+                self.assert_belongs_to_pool(*address, String::from("Weighted Remove Liquidity"));
+            }
+
+            // Calculating the percentage ownership that the tracking tokens amount corresponds to
+            let tracking_tokens_manager: ResourceManager = borrow_resource_manager!(self.tracking_token_address);
+            let percentage: Decimal = tracking_tokens.amount() / tracking_tokens_manager.total_supply();
+
+            info!("User about to withdraw {} of the liquidity", percentage);
+
+            // Burning the tracking tokens
+            self.tracking_token_admin_badge.authorize(|| {
+                tracking_tokens.burn();
+            });
+
+            // Withdrawing the amount of tokens owed to this liquidity provider
+            let mut buckets: Vec<Bucket> = Vec::new();
+            for address in self.addresses() {
+                let amount: Decimal = self.vaults[&address].amount() * percentage;
+                let min_amount: Decimal = min_amounts.get(&address).cloned().unwrap_or(Decimal::zero());
+                assert!(
+                    amount >= min_amount,
+                    "[Weighted Remove Liquidity]: The amount of {:?} withdrawn ({}) is below the minimum of {}. The \
+                    reserves may have moved.",
+                    address, amount, min_amount
+                );
+                buckets.push(self.withdraw(address, amount));
+            }
+
+            return buckets;
+        }
+
+        /// Performs the swap of tokens and takes the pool fee in the process
+        /// 
+        /// This is a low level method that does not perform checks on slippage. It is up to the caller of this method
+        /// to perform the checks needed. The share of the fee set by `protocol_fee` is diverted into the protocol 
+        /// vaults.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the weighted pool is not paused.
+        /// * **Check 2:** Checks that both tokens belong to this liquidity pool and are not the same.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap(
+            &mut self,
+            mut tokens: Bucket,
+            output_resource_address: ResourceAddress
+        ) -> Bucket {
+            self.assert_not_paused(String::from("Weighted Swap"));
+            self.assert_valid_pair(tokens.resource_address(), output_resource_address, String::from("Weighted Swap"));
+            info!("[Weighted Swap]: Invariant before swap: {}", self.invariant());
+
+            // Calculating the output amount for the given input amount of tokens and withdrawing it from the vault
+            let output_amount: Decimal = self.calculate_output_amount(
+                tokens.resource_address(),
+                output_resource_address,
+                tokens.amount()
+            );
+            info!("[Weighted Swap]: output amount is : {}", output_amount);
+            let output_tokens: Bucket = self.withdraw(output_resource_address, output_amount);
+
+            // Depositing the tokens, less the protocol's share of the fee, into the liquidity pool and returning a 
+            // bucket of the swapped tokens.
+            self.take_protocol_fee(&mut tokens);
+            self.deposit(tokens);
+            info!("[Weighted Swap]: Invariant after swap: {}", self.invariant());
+            return output_tokens;
+        }
+
+        /// Swaps all of the given tokens for the output token.
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the weighted pool is not paused.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the input tokens that will be swapped for other tokens.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `min_amount_out` (Decimal) - The minimum amount of tokens that the caller is willing to accept before the
+        /// method fails.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        pub fn swap_exact_tokens_for_tokens(
+            &mut self,
+            tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            min_amount_out: Decimal
+        ) -> Bucket {
+            self.assert_not_paused(String::from("Weighted Swap Exact"));
+            let output_tokens: Bucket = self.swap(tokens, output_resource_address);
+            assert!(output_tokens.amount() >= min_amount_out, "[Weighted Swap Exact]: min_amount_out not satisfied.");
+
+            return output_tokens;
+        }
+
+        /// Swaps tokens for a specific amount of the output token
+        /// 
+        /// This method performs a number of checks before the swap is performed:
+        /// 
+        /// * **Check 1:** Checks that the weighted pool is not paused.
+        /// * **Check 2:** Checks that both tokens belong to this liquidity pool and are not the same.
+        /// * **Check 3:** Checks that the tokens bucket contains the input required.
+        /// 
+        /// # Arguments:
+        /// 
+        /// * `tokens` (Bucket) - A bucket containing the tokens that the user wishes to swap.
+        /// * `output_resource_address` (ResourceAddress) - The resource address of the token to receive from the swap.
+        /// * `output_amount` (Decimal) - A decimal of the specific amount of output that the user wishes to receive
+        /// from this swap.
+        /// 
+        /// # Returns:
+        /// 
+        /// * `Bucket` - A bucket of the output tokens.
+        /// * `Bucket` - A bucket of the remaining input tokens.
+        pub fn swap_tokens_for_exact_tokens(
+            &mut self,
+            mut tokens: Bucket,
+            output_resource_address: ResourceAddress,
+            output_amount: Decimal
+        ) -> (Bucket, Bucket) {
+            self.assert_not_paused(String::from("Weighted Swap For Exact"));
+
+            // Calculating the amount of input tokens that would be required to produce the desired amount of output
+            // tokens
+            let input_required: Decimal = self.calculate_input_amount(
+                tokens.resource_address(),
+                output_resource_address,
+                output_amount
+            );
+            assert!(
+                tokens.amount() >= input_required,
+                "[Weighted Swap For Exact]: Not enough input for the desired amount of output. Input required is {}",
+                input_required
+            );
+
+            // Depositing the amount of input required into the vaults and taking out the requested amount
+            let mut input_tokens: Bucket = tokens.take(input_required);
+            self.take_protocol_fee(&mut input_tokens);
+            self.deposit(input_tokens);
+            let output_tokens: Bucket = self.withdraw(output_resource_address, output_amount);
+            info!("[Weighted Swap For Exact]: Invariant after swap: {}", self.invariant());
+            return (output_tokens, tokens);
+        }
+    }
+}